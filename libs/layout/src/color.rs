@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A process color; components are percentages (0-100).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CmykColor {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+    pub name: String,
+}
+
+impl CmykColor {
+    /// `#rrggbb` preview of this color.
+    pub fn to_rgb_hex(&self) -> String {
+        let (r, g, b) = cmyk_to_srgb(self);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+impl std::fmt::Display for CmykColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (C:{:.0} M:{:.0} Y:{:.0} K:{:.0})",
+            self.name, self.c, self.m, self.y, self.k
+        )
+    }
+}
+
+/// Naive CMYK to sRGB conversion for screen previews (no ICC profile).
+pub fn cmyk_to_srgb(color: &CmykColor) -> (u8, u8, u8) {
+    let unit = |v: f64| (v / 100.0).clamp(0.0, 1.0);
+    let k = unit(color.k);
+    let channel = |v: f64| (255.0 * (1.0 - unit(v)) * (1.0 - k)).round() as u8;
+    (channel(color.c), channel(color.m), channel(color.y))
+}
+
+/// The named colors of a project, shared by intake and the renderers.
+/// Fills name a color here by `color_ref`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColorPalette {
+    pub colors: BTreeMap<String, CmykColor>,
+    /// Spot inks by name, such as `PMS 185 C`. They have no process
+    /// equivalent here, so [`ColorPalette::get`] doesn't resolve them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spots: Vec<String>,
+}
+
+impl ColorPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: &str, color: CmykColor) {
+        self.colors.insert(name.to_string(), color);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CmykColor> {
+        self.colors.get(name)
+    }
+
+    pub fn to_rgb_hex(&self, name: &str) -> Option<String> {
+        self.get(name).map(CmykColor::to_rgb_hex)
+    }
+
+    /// Add a spot ink, unless it's already listed.
+    pub fn add_spot(&mut self, name: &str) {
+        if !self.is_spot(name) {
+            self.spots.push(name.to_string());
+        }
+    }
+
+    pub fn is_spot(&self, name: &str) -> bool {
+        self.spots.iter().any(|s| s == name)
+    }
+}
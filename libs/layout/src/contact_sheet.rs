@@ -0,0 +1,85 @@
+use crate::page::{Block, BlockContent, Page, PageSize};
+use crate::planner::suggest_grid;
+use crate::provenance::DecisionTree;
+
+/// Lay out every moodboard reference the tree cites as one image tile per
+/// reference, captioned with its description. Tiles point at
+/// `moodboard/<board_id>/<ref_id>`; references without a file there render
+/// as the usual image placeholder.
+pub fn moodboard_contact_sheet(tree: &DecisionTree, size: PageSize) -> Page {
+    let refs = tree.moodboard_refs();
+    let mut page = Page::new(1, size, 1, 1);
+    page.grid = suggest_grid(size, refs.len(), 1.0);
+
+    let columns = page.grid.columns;
+    for (i, r) in refs.into_iter().enumerate() {
+        let i = i as u32;
+        page.add_block(Block {
+            id: format!("{}/{}", r.board_id, r.ref_id),
+            col: i % columns,
+            row: i / columns,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Image {
+                path: format!("moodboard/{}/{}", r.board_id, r.ref_id),
+                alt: r.description,
+            },
+            decision_ids: Vec::new(),
+            clip: true,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+    }
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::{Decision, DecisionKind, Influence, InfluenceSource};
+
+    fn citing(id: &str, refs: &[&str]) -> Decision {
+        Decision {
+            id: id.to_string(),
+            parent_id: None,
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            agent: "art-direction".to_string(),
+            kind: DecisionKind::StyleDirection {
+                description: format!("direction {id}"),
+            },
+            rationale: String::new(),
+            influences: refs
+                .iter()
+                .map(|r| Influence {
+                    source: InfluenceSource::Moodboard {
+                        board_id: "spring".to_string(),
+                        ref_id: r.to_string(),
+                        description: format!("ref {r}"),
+                    },
+                    relevance: String::new(),
+                    weight: 0.5,
+                })
+                .collect(),
+            alternatives_considered: Vec::new(),
+            confidence: 0.7,
+            tags: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn three_distinct_refs_make_three_tiles() {
+        let mut tree = DecisionTree::new("p");
+        tree.add(citing("type", &["poster.jpg", "signage.jpg"]));
+        tree.add(citing("color", &["signage.jpg", "fabric.jpg"]));
+
+        let page = moodboard_contact_sheet(&tree, PageSize::Letter);
+        let ids: Vec<&str> = page.blocks.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["spring/poster.jpg", "spring/signage.jpg", "spring/fabric.jpg"]);
+        assert!(page.blocks.iter().all(|b| page.grid.contains(b.col, b.row, 1, 1)));
+        assert!(matches!(
+            &page.blocks[2].content,
+            BlockContent::Image { alt, .. } if alt == "ref fabric.jpg"
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::page::Page;
+
+/// A multi-page piece -- a zine, an editorial -- with its pages kept in
+/// page-number order.
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub project_id: String,
+    pub title: String,
+    pages: Vec<Page>,
+}
+
+/// Why a page couldn't be added to a [`Document`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentError {
+    /// The document already has a page with this number.
+    DuplicatePage(u32),
+}
+
+impl std::fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicatePage(n) => write!(f, "document already has a page {n}"),
+        }
+    }
+}
+
+impl std::error::Error for DocumentError {}
+
+impl Document {
+    pub fn new(project_id: &str, title: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            title: title.to_string(),
+            pages: Vec::new(),
+        }
+    }
+
+    /// A document holding `pages`, rejecting duplicate page numbers.
+    pub fn from_pages(
+        project_id: &str,
+        title: &str,
+        pages: Vec<Page>,
+    ) -> Result<Self, DocumentError> {
+        let mut doc = Self::new(project_id, title);
+        for page in pages {
+            doc.add_page(page)?;
+        }
+        Ok(doc)
+    }
+
+    /// Insert `page` in page-number order. A page whose grid is bound into a
+    /// spread is put on the side its number calls for.
+    pub fn add_page(&mut self, mut page: Page) -> Result<(), DocumentError> {
+        match self.pages.binary_search_by_key(&page.number, |p| p.number) {
+            Ok(_) => Err(DocumentError::DuplicatePage(page.number)),
+            Err(i) => {
+                page.bind_if_facing();
+                self.pages.insert(i, page);
+                Ok(())
+            }
+        }
+    }
+
+    /// The page numbered `number`, if there is one.
+    pub fn page(&self, number: u32) -> Option<&Page> {
+        self.pages
+            .binary_search_by_key(&number, |p| p.number)
+            .ok()
+            .map(|i| &self.pages[i])
+    }
+
+    pub fn page_mut(&mut self, number: u32) -> Option<&mut Page> {
+        self.pages
+            .binary_search_by_key(&number, |p| p.number)
+            .ok()
+            .map(|i| &mut self.pages[i])
+    }
+
+    /// All pages, in page-number order.
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::document::DocumentError;
+#[cfg(feature = "idml")]
+use crate::idml::IdmlError;
+use crate::page::{BlockError, PageSizeParseError};
+use crate::provenance::{ProvenanceError, ReplaceError};
+
+/// Errors from the layout crate's fallible APIs.
+#[derive(Debug)]
+pub enum LayoutError {
+    /// A grid that can't hold its cells: no columns or rows, or margins and
+    /// gutters that leave no room.
+    InvalidGrid(String),
+    /// Input that parsed but doesn't describe a usable layout.
+    Validation(String),
+    /// A page that couldn't be rendered in the requested format.
+    Render(String),
+    PageSize(PageSizeParseError),
+    Block(BlockError),
+    Document(DocumentError),
+    Replace(ReplaceError),
+    Provenance(ProvenanceError),
+    #[cfg(feature = "idml")]
+    Idml(IdmlError),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidGrid(msg) => write!(f, "invalid grid: {msg}"),
+            Self::Validation(msg) => write!(f, "invalid layout: {msg}"),
+            Self::Render(msg) => write!(f, "render failed: {msg}"),
+            Self::PageSize(e) => e.fmt(f),
+            Self::Block(e) => e.fmt(f),
+            Self::Document(e) => e.fmt(f),
+            Self::Replace(e) => write!(f, "decision tree: {e}"),
+            Self::Provenance(e) => write!(f, "decision tree: {e}"),
+            #[cfg(feature = "idml")]
+            Self::Idml(e) => e.fmt(f),
+            Self::Io(e) => write!(f, "i/o error: {e}"),
+            Self::Json(e) => write!(f, "json error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::PageSize(e) => Some(e),
+            Self::Block(e) => Some(e),
+            Self::Document(e) => Some(e),
+            Self::Replace(e) => Some(e),
+            Self::Provenance(e) => Some(e),
+            #[cfg(feature = "idml")]
+            Self::Idml(e) => Some(e),
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LayoutError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LayoutError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<ReplaceError> for LayoutError {
+    fn from(e: ReplaceError) -> Self {
+        Self::Replace(e)
+    }
+}
+
+impl From<ProvenanceError> for LayoutError {
+    fn from(e: ProvenanceError) -> Self {
+        Self::Provenance(e)
+    }
+}
+
+impl From<PageSizeParseError> for LayoutError {
+    fn from(e: PageSizeParseError) -> Self {
+        Self::PageSize(e)
+    }
+}
+
+impl From<BlockError> for LayoutError {
+    fn from(e: BlockError) -> Self {
+        Self::Block(e)
+    }
+}
+
+impl From<DocumentError> for LayoutError {
+    fn from(e: DocumentError) -> Self {
+        Self::Document(e)
+    }
+}
+
+#[cfg(feature = "idml")]
+impl From<IdmlError> for LayoutError {
+    fn from(e: IdmlError) -> Self {
+        Self::Idml(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Margin};
+
+    #[test]
+    fn zero_column_grid_is_an_invalid_grid_error() {
+        let err = Grid::try_with_margin(0, 4, 612.0, 792.0, Margin::default()).unwrap_err();
+        assert!(matches!(err, LayoutError::InvalidGrid(_)));
+        assert_eq!(
+            err.to_string(),
+            "invalid grid: 0 x 4 grid needs at least one column and one row"
+        );
+    }
+}
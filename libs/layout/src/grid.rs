@@ -1,14 +1,81 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::LayoutError;
+use crate::page::PageSize;
+use crate::units::{pt_to_in, pt_to_mm};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Grid {
     pub columns: u32,
     pub rows: u32,
+    /// Width of every column, or of the narrowest one when `column_tracks`
+    /// is set.
     pub column_width: f64,
+    /// Height of every row, or of the shortest one when `row_tracks` is set.
     pub row_height: f64,
     pub gutter_h: f64,
     pub gutter_v: f64,
     pub margin: Margin,
+    /// Baseline grid increment in points, measured down from the top
+    /// margin; 0 turns baseline snapping off.
+    #[serde(default = "default_baseline")]
+    pub baseline: f64,
+    /// Per-column widths in points, left to right; empty for uniform columns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_tracks: Vec<f64>,
+    /// Per-row heights in points, top to bottom; empty for uniform rows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub row_tracks: Vec<f64>,
+    /// Which side of a spread the page sits on; verso pages print with
+    /// mirrored margins.
+    #[serde(default)]
+    pub binding: Binding,
+}
+
+/// Default baseline increment: the leading of 10pt text at 1.4 line height.
+pub const DEFAULT_BASELINE: f64 = 14.0;
+
+fn default_baseline() -> f64 {
+    DEFAULT_BASELINE
+}
+
+/// Where a page sits relative to the spine. Margins are authored for a
+/// recto page, with `left` as the inner (binding) margin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Binding {
+    /// Not part of a spread; margins apply as authored.
+    #[default]
+    None,
+    /// A right-hand page: the inner margin is on the left.
+    Recto,
+    /// A left-hand page: the inner margin is on the right.
+    Verso,
+}
+
+/// An axis-aligned rectangle in points; `y` grows downward.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Whether `(x, y)` lies inside the rect or on its edge.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.x + self.w && y >= self.y && y <= self.y + self.h
+    }
+
+    /// Grow the rect by `by` on every side.
+    pub fn expand(&self, by: f64) -> Rect {
+        Rect::new(self.x - by, self.y - by, self.w + 2.0 * by, self.h + 2.0 * by)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +86,17 @@ pub struct Margin {
     pub left: f64,
 }
 
+impl Margin {
+    /// The same margins with left and right swapped, as on the facing page.
+    pub fn mirrored(&self) -> Margin {
+        Margin {
+            left: self.right,
+            right: self.left,
+            ..self.clone()
+        }
+    }
+}
+
 impl Default for Margin {
     fn default() -> Self {
         Self {
@@ -32,11 +110,34 @@ impl Default for Margin {
 
 impl Grid {
     pub fn new(columns: u32, rows: u32, page_width: f64, page_height: f64) -> Self {
-        let margin = Margin::default();
+        Self::with_margin(columns, rows, page_width, page_height, Margin::default())
+    }
+
+    /// Like [`Grid::new`] but with explicit per-edge margins.
+    pub fn with_margin(
+        columns: u32,
+        rows: u32,
+        page_width: f64,
+        page_height: f64,
+        margin: Margin,
+    ) -> Self {
+        Self::with_gutter(columns, rows, page_width, page_height, margin, 12.0)
+    }
+
+    /// Like [`Grid::with_margin`] but with `gutter` points between columns
+    /// and between rows.
+    pub fn with_gutter(
+        columns: u32,
+        rows: u32,
+        page_width: f64,
+        page_height: f64,
+        margin: Margin,
+        gutter: f64,
+    ) -> Self {
         let usable_w = page_width - margin.left - margin.right;
         let usable_h = page_height - margin.top - margin.bottom;
-        let gutter_h = 12.0;
-        let gutter_v = 12.0;
+        let gutter_h = gutter;
+        let gutter_v = gutter;
         let col_w = (usable_w - gutter_h * (columns as f64 - 1.0)) / columns as f64;
         let row_h = (usable_h - gutter_v * (rows as f64 - 1.0)) / rows as f64;
 
@@ -48,20 +149,346 @@ impl Grid {
             gutter_h,
             gutter_v,
             margin,
+            binding: Binding::None,
+            baseline: DEFAULT_BASELINE,
+            column_tracks: Vec::new(),
+            row_tracks: Vec::new(),
         }
     }
 
+    /// A grid whose columns and rows have their own sizes, in points, with
+    /// `gutter` between each. A 2:1:1 split of 400pt is
+    /// `vec![200.0, 100.0, 100.0]`.
+    pub fn with_tracks(
+        column_tracks: Vec<f64>,
+        row_tracks: Vec<f64>,
+        gutter: f64,
+        margin: Margin,
+    ) -> Self {
+        let narrowest = |tracks: &[f64]| tracks.iter().copied().reduce(f64::min).unwrap_or(0.0);
+        Self {
+            columns: column_tracks.len() as u32,
+            rows: row_tracks.len() as u32,
+            column_width: narrowest(&column_tracks),
+            row_height: narrowest(&row_tracks),
+            gutter_h: gutter,
+            gutter_v: gutter,
+            margin,
+            binding: Binding::None,
+            baseline: DEFAULT_BASELINE,
+            column_tracks,
+            row_tracks,
+        }
+    }
+
+    pub fn column_width_mm(&self) -> f64 {
+        pt_to_mm(self.column_width)
+    }
+
+    pub fn column_width_inches(&self) -> f64 {
+        pt_to_in(self.column_width)
+    }
+
+    pub fn row_height_mm(&self) -> f64 {
+        pt_to_mm(self.row_height)
+    }
+
+    pub fn row_height_inches(&self) -> f64 {
+        pt_to_in(self.row_height)
+    }
+
+    /// Width of column `col`.
+    pub fn column_track(&self, col: u32) -> f64 {
+        self.column_tracks
+            .get(col as usize)
+            .copied()
+            .unwrap_or(self.column_width)
+    }
+
+    /// Height of row `row`.
+    pub fn row_track(&self, row: u32) -> f64 {
+        self.row_tracks
+            .get(row as usize)
+            .copied()
+            .unwrap_or(self.row_height)
+    }
+
+    /// A grid with as many columns as best fit `target_col_width`: the
+    /// count whose resulting `column_width` is closest to the target,
+    /// preferring more columns on a tie. Check `column_width` on the result
+    /// for how far it drifted.
+    pub fn fit_columns(
+        page_width: f64,
+        page_height: f64,
+        rows: u32,
+        target_col_width: f64,
+        gutter: f64,
+        margin: Margin,
+    ) -> Self {
+        let available = page_width - margin.left - margin.right;
+        let columns = fit_track_count(available, target_col_width, gutter);
+        Self::with_gutter(columns, rows, page_width, page_height, margin, gutter)
+    }
+
+    /// Like [`Grid::fit_columns`], fitting rows to `target_row_height`.
+    pub fn fit_rows(
+        page_width: f64,
+        page_height: f64,
+        columns: u32,
+        target_row_height: f64,
+        gutter: f64,
+        margin: Margin,
+    ) -> Self {
+        let available = page_height - margin.top - margin.bottom;
+        let rows = fit_track_count(available, target_row_height, gutter);
+        Self::with_gutter(columns, rows, page_width, page_height, margin, gutter)
+    }
+
+    /// The grid placed on one side of a spread.
+    pub fn with_binding(mut self, binding: Binding) -> Self {
+        self.binding = binding;
+        self
+    }
+
+    /// The margins as they print: mirrored on a verso page, as authored
+    /// otherwise.
+    pub fn effective_margin(&self) -> Margin {
+        match self.binding {
+            Binding::Verso => self.margin.mirrored(),
+            Binding::None | Binding::Recto => self.margin.clone(),
+        }
+    }
+
+    /// Like [`Grid::with_margin`], but refuses grids with no columns or
+    /// rows, or whose margins and gutters leave no room for the cells.
+    pub fn try_with_margin(
+        columns: u32,
+        rows: u32,
+        page_width: f64,
+        page_height: f64,
+        margin: Margin,
+    ) -> Result<Self, LayoutError> {
+        if columns == 0 || rows == 0 {
+            return Err(LayoutError::InvalidGrid(format!(
+                "{columns} x {rows} grid needs at least one column and one row"
+            )));
+        }
+        let grid = Self::with_margin(columns, rows, page_width, page_height, margin);
+        if grid.column_width <= 0.0 || grid.row_height <= 0.0 {
+            return Err(LayoutError::InvalidGrid(format!(
+                "{columns} x {rows} grid leaves no room for its cells"
+            )));
+        }
+        Ok(grid)
+    }
+
+    /// A grid whose content area is centered on the page: `total_margin` is
+    /// the space left over on each axis, split evenly between opposite edges.
+    pub fn centered(
+        columns: u32,
+        rows: u32,
+        page_width: f64,
+        page_height: f64,
+        total_margin: f64,
+    ) -> Self {
+        let half = total_margin / 2.0;
+        let margin = Margin {
+            top: half,
+            right: half,
+            bottom: half,
+            left: half,
+        };
+        Self::with_margin(columns, rows, page_width, page_height, margin)
+    }
+
+    /// Names accepted by [`Grid::preset`].
+    pub const PRESETS: &[&str] = &["swiss-12col", "6col-editorial", "golden-section", "manuscript"];
+
+    /// Build a well-known grid system for `page`, or `None` for an unknown name.
+    pub fn preset(name: &str, page: PageSize) -> Option<Grid> {
+        let (w, h) = page.dimensions();
+        let grid = match name {
+            "swiss-12col" => Grid::new(12, 8, w, h),
+            "6col-editorial" => {
+                let margin = Margin {
+                    top: 54.0,
+                    right: 42.0,
+                    bottom: 72.0,
+                    left: 42.0,
+                };
+                Grid::with_margin(6, 9, w, h, margin)
+            }
+            "golden-section" => {
+                // Margins step by the golden ratio: inner < top < outer < bottom.
+                let phi = (1.0 + 5f64.sqrt()) / 2.0;
+                let inner = w / 16.0;
+                let margin = Margin {
+                    top: inner * phi,
+                    right: inner * phi * phi,
+                    bottom: inner * phi * phi * phi,
+                    left: inner,
+                };
+                Grid::with_margin(5, 8, w, h, margin)
+            }
+            "manuscript" => Grid::new(1, 1, w, h),
+            _ => return None,
+        };
+        Some(grid)
+    }
+
+    /// Every `(col, row)` in the grid, row-major: left to right, then top to
+    /// bottom.
+    pub fn cells(&self) -> impl Iterator<Item = (u32, u32)> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |row| (0..columns).map(move |col| (col, row)))
+    }
+
+    /// Whether a block starting at `(col, row)` and spanning at least one
+    /// cell each way fits entirely inside the grid.
+    pub fn contains(&self, col: u32, row: u32, col_span: u32, row_span: u32) -> bool {
+        let fits = |start: u32, span: u32, limit: u32| {
+            span > 0 && start.checked_add(span).is_some_and(|end| end <= limit)
+        };
+        fits(col, col_span, self.columns) && fits(row, row_span, self.rows)
+    }
+
     /// Returns (x, y) of the top-left corner of a cell.
     pub fn cell_origin(&self, col: u32, row: u32) -> (f64, f64) {
-        let x = self.margin.left + col as f64 * (self.column_width + self.gutter_h);
-        let y = self.margin.top + row as f64 * (self.row_height + self.gutter_v);
+        let x = self.effective_margin().left
+            + track_sum(&self.column_tracks, self.column_width, 0, col)
+            + col as f64 * self.gutter_h;
+        let y = self.margin.top
+            + track_sum(&self.row_tracks, self.row_height, 0, row)
+            + row as f64 * self.gutter_v;
         (x, y)
     }
 
-    /// Returns (width, height) for a block spanning multiple cells.
+    /// Returns (width, height) for a block spanning `col_span` x `row_span`
+    /// cells from the top-left cell, summing tracks and gutters. On a tracked
+    /// grid the size depends on where the block starts; see
+    /// [`Grid::cell_rect`].
     pub fn span_size(&self, col_span: u32, row_span: u32) -> (f64, f64) {
-        let w = col_span as f64 * self.column_width + (col_span as f64 - 1.0) * self.gutter_h;
-        let h = row_span as f64 * self.row_height + (row_span as f64 - 1.0) * self.gutter_v;
+        let Rect { w, h, .. } = self.cell_rect(0, 0, col_span, row_span);
         (w, h)
     }
+
+    /// The first baseline at or below `y`, counting baselines down from the
+    /// top margin. `y` is returned unchanged when it's above the top margin
+    /// or the baseline grid is off.
+    pub fn snap_to_baseline(&self, y: f64) -> f64 {
+        let top = self.margin.top;
+        if self.baseline <= 0.0 || y <= top {
+            return y;
+        }
+        top + ((y - top) / self.baseline).ceil() * self.baseline
+    }
+
+    /// The y of every baseline from the top margin to the bottom of the
+    /// last row, for drawing baseline guides.
+    pub fn baseline_lines(&self) -> Vec<f64> {
+        if self.baseline <= 0.0 || self.rows == 0 {
+            return Vec::new();
+        }
+        let bottom = self.cell_rect(0, 0, 1, self.rows);
+        let bottom = bottom.y + bottom.h;
+        (1..)
+            .map(|n| self.margin.top + n as f64 * self.baseline)
+            .take_while(|&y| y <= bottom)
+            .collect()
+    }
+
+    /// The rectangle covered by a block starting at `(col, row)`, summing
+    /// the tracks it spans and the gutters between them.
+    pub fn cell_rect(&self, col: u32, row: u32, col_span: u32, row_span: u32) -> Rect {
+        let (x, y) = self.cell_origin(col, row);
+        let w = track_sum(&self.column_tracks, self.column_width, col, col_span)
+            + col_span.saturating_sub(1) as f64 * self.gutter_h;
+        let h = track_sum(&self.row_tracks, self.row_height, row, row_span)
+            + row_span.saturating_sub(1) as f64 * self.gutter_v;
+        Rect::new(x, y, w, h)
+    }
+}
+
+/// The summed size of `n` tracks from `start`, with `uniform` standing in
+/// for any track past the end of `tracks`. Costs no more than one pass over
+/// `tracks`, however large `n` is.
+fn track_sum(tracks: &[f64], uniform: f64, start: u32, n: u32) -> f64 {
+    let (start, end) = (start as usize, start.saturating_add(n) as usize);
+    let listed = &tracks[start.min(tracks.len())..end.min(tracks.len())];
+    listed.iter().sum::<f64>() + (end - start - listed.len()) as f64 * uniform
+}
+
+/// How many tracks of about `target` points, `gutter` apart, best fill
+/// `available` points. At least one.
+pub fn fit_track_count(available: f64, target: f64, gutter: f64) -> u32 {
+    if target <= 0.0 || available <= 0.0 {
+        return 1;
+    }
+    let size = |n: f64| (available - gutter * (n - 1.0)) / n;
+    let estimate = ((available + gutter) / (target + gutter)).max(1.0);
+    let (low, high) = (estimate.floor().max(1.0), estimate.ceil().max(1.0));
+    let best = if (size(high) - target).abs() <= (size(low) - target).abs() {
+        high
+    } else {
+        low
+    };
+    best as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_margin() -> Margin {
+        Margin {
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+            left: 0.0,
+        }
+    }
+
+    #[test]
+    fn two_one_one_split_origins_and_spans() {
+        let grid = Grid::with_tracks(vec![200.0, 100.0, 100.0], vec![300.0], 10.0, no_margin());
+        assert_eq!(grid.cell_origin(0, 0), (0.0, 0.0));
+        assert_eq!(grid.cell_origin(1, 0), (210.0, 0.0));
+        assert_eq!(grid.cell_origin(2, 0), (320.0, 0.0));
+        assert_eq!(grid.cell_rect(1, 0, 2, 1), Rect::new(210.0, 0.0, 210.0, 300.0));
+        assert_eq!(grid.span_size(1, 1), (200.0, 300.0));
+        assert_eq!(grid.span_size(3, 1), (420.0, 300.0));
+    }
+
+    #[test]
+    fn span_size_matches_uniform_grid() {
+        let grid = Grid::with_gutter(4, 2, 472.0, 200.0, no_margin(), 12.0);
+        assert_eq!(grid.span_size(2, 1), (2.0 * 109.0 + 12.0, 94.0));
+    }
+
+    #[test]
+    fn swiss_preset_on_a4_has_twelve_usable_columns() {
+        let grid = Grid::preset("swiss-12col", PageSize::A4).unwrap();
+        assert_eq!(grid.columns, 12);
+        for col in 0..grid.columns {
+            assert!(grid.cell_rect(col, 0, 1, 1).w > 0.0, "column {col}");
+        }
+        assert!(Grid::preset("no-such-system", PageSize::A4).is_none());
+    }
+
+    #[test]
+    fn cells_run_left_to_right_then_down() {
+        let grid = Grid::new(2, 3, 612.0, 792.0);
+        let cells: Vec<(u32, u32)> = grid.cells().collect();
+        assert_eq!(cells, [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn centered_grid_has_equal_side_margins() {
+        let grid = Grid::centered(4, 6, 612.0, 792.0, 96.0);
+        assert_eq!(grid.margin.left, grid.margin.right);
+        assert_eq!(grid.margin.top, grid.margin.bottom);
+        let content = grid.cell_rect(0, 0, 4, 6);
+        assert_eq!(content.x, 612.0 - (content.x + content.w));
+        assert_eq!(content.y, 792.0 - (content.y + content.h));
+    }
 }
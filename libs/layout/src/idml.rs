@@ -0,0 +1,367 @@
+//! IDML (InDesign Markup Language) export. A package is a ZIP of XML parts,
+//! laid out like the Python exporter in `scripts/src/grids/typeset/idml.py`:
+//!
+//!   - mimetype                -- stored first, uncompressed
+//!   - META-INF/container.xml  -- package identifier
+//!   - designmap.xml           -- document manifest
+//!   - Resources/Fonts.xml     -- font families used by text
+//!   - Resources/Styles.xml    -- one paragraph style per text style
+//!   - Resources/Graphic.xml   -- CMYK swatches
+//!   - Spreads/Spread_N.xml    -- the page with its frames
+//!   - Stories/Story_*.xml     -- text content per frame
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::color::CmykColor;
+use crate::grid::Rect;
+use crate::page::{Block, BlockContent, Page, TextStyle};
+use crate::render::xml_escape;
+
+const MIMETYPE: &str = "application/vnd.adobe.indesign-idml-package+xml";
+const DOM_VERSION: &str = "18.0";
+const PACKAGING_NS: &str = "http://ns.adobe.com/AdobeInDesign/idml/1.0/packaging";
+const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+/// Why an IDML package couldn't be written.
+#[derive(Debug)]
+pub enum IdmlError {
+    Zip(zip::result::ZipError),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for IdmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Zip(e) => write!(f, "writing IDML package: {e}"),
+            Self::Io(e) => write!(f, "writing IDML package: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for IdmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Zip(e) => Some(e),
+            Self::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for IdmlError {
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::Zip(e)
+    }
+}
+
+impl From<std::io::Error> for IdmlError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// A text frame's story: its paragraphs and the paragraph style they use.
+struct Story {
+    id: String,
+    style: String,
+    paragraphs: Vec<String>,
+}
+
+/// Export a page as an IDML package InDesign can open. Text and table
+/// blocks become text frames with their own stories; fills, images and QR
+/// codes become rectangles at the block's position. Empty blocks are left
+/// out.
+pub fn page_to_idml(page: &Page) -> Result<Vec<u8>, IdmlError> {
+    let mut styles: BTreeMap<String, &TextStyle> = BTreeMap::new();
+    let mut colors: BTreeMap<String, CmykColor> = BTreeMap::new();
+    colors.insert("Black".to_string(), black());
+    let mut stories = Vec::new();
+    let mut items = String::new();
+
+    for (i, block) in page.blocks.iter().enumerate() {
+        let self_id = format!("u{i}");
+        match &block.content {
+            BlockContent::Text {
+                body,
+                style,
+                style_ref,
+            } => {
+                let resolved = page.resolve_style(style, style_ref.as_deref());
+                let style_name = match style_ref {
+                    Some(name) if page.styles.contains_key(name) => name.clone(),
+                    _ => format!("Block {}", block.id),
+                };
+                register_style(&mut styles, &mut colors, &style_name, resolved);
+                items.push_str(&text_frame(page, block, &self_id));
+                stories.push(Story {
+                    id: self_id,
+                    style: style_name,
+                    paragraphs: body.split('\n').map(str::to_string).collect(),
+                });
+            }
+            BlockContent::Table {
+                headers,
+                rows,
+                style,
+            } => {
+                let style_name = format!("Block {}", block.id);
+                register_style(&mut styles, &mut colors, &style_name, style);
+                items.push_str(&text_frame(page, block, &self_id));
+                // Cells are tab-separated; InDesign can convert the text
+                // back into a table.
+                let paragraphs = std::iter::once(headers)
+                    .chain(rows.iter())
+                    .map(|cells| cells.join("\t"))
+                    .collect();
+                stories.push(Story {
+                    id: self_id,
+                    style: style_name,
+                    paragraphs,
+                });
+            }
+            BlockContent::Fill { color, color_ref } => {
+                let color = page.resolve_color(color, color_ref.as_deref());
+                let swatch = swatch_name(color);
+                colors.insert(swatch.clone(), color.clone());
+                items.push_str(&rectangle(page, block, &self_id, Some(&swatch)));
+            }
+            BlockContent::Image { .. } | BlockContent::Qr { .. } => {
+                items.push_str(&rectangle(page, block, &self_id, None));
+            }
+            BlockContent::Empty => {}
+        }
+    }
+
+    let spread_id = page.number.to_string();
+    let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut part = |name: &str, body: &str| -> Result<(), IdmlError> {
+        zip.start_file(name, stored)?;
+        zip.write_all(body.as_bytes())?;
+        Ok(())
+    };
+
+    part("mimetype", MIMETYPE)?;
+    part("META-INF/container.xml", &container_xml())?;
+    part("designmap.xml", &designmap_xml(page, &spread_id, &stories))?;
+    part("Resources/Fonts.xml", &fonts_xml(&styles))?;
+    part("Resources/Styles.xml", &styles_xml(&styles))?;
+    part("Resources/Graphic.xml", &graphic_xml(&colors))?;
+    part(
+        &format!("Spreads/Spread_{spread_id}.xml"),
+        &spread_xml(page, &spread_id, &items),
+    )?;
+    for story in &stories {
+        part(
+            &format!("Stories/Story_{}.xml", story.id),
+            &story_xml(story),
+        )?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+fn black() -> CmykColor {
+    CmykColor {
+        c: 0.0,
+        m: 0.0,
+        y: 0.0,
+        k: 100.0,
+        name: "Black".to_string(),
+    }
+}
+
+/// The swatch a color is filed under: its name, or InDesign's own
+/// `C=.. M=.. Y=.. K=..` naming when it has none.
+fn swatch_name(color: &CmykColor) -> String {
+    if color.name.is_empty() {
+        format!(
+            "C={:.0} M={:.0} Y={:.0} K={:.0}",
+            color.c, color.m, color.y, color.k
+        )
+    } else {
+        color.name.clone()
+    }
+}
+
+fn register_style<'a>(
+    styles: &mut BTreeMap<String, &'a TextStyle>,
+    colors: &mut BTreeMap<String, CmykColor>,
+    name: &str,
+    style: &'a TextStyle,
+) {
+    if let Some(color) = &style.color {
+        colors.insert(swatch_name(color), color.clone());
+    }
+    styles.insert(name.to_string(), style);
+}
+
+/// `GeometricBounds`-ordered corners of a block (top, left, bottom, right),
+/// in page coordinates.
+fn block_bounds(page: &Page, block: &Block) -> (f64, f64, f64, f64) {
+    let Rect { x, y, w, h } =
+        page.grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+    (y, x, y + h, x + w)
+}
+
+/// A closed rectangular path for a page item.
+fn path_geometry(page: &Page, block: &Block) -> String {
+    let (top, left, bottom, right) = block_bounds(page, block);
+    let points: String = [(left, top), (left, bottom), (right, bottom), (right, top)]
+        .iter()
+        .map(|(x, y)| {
+            format!(
+                "<PathPointType Anchor=\"{x} {y}\" LeftDirection=\"{x} {y}\" RightDirection=\"{x} {y}\"/>"
+            )
+        })
+        .collect();
+    format!(
+        "<Properties><PathGeometry><GeometryPathType PathOpen=\"false\"><PathPointArray>{points}</PathPointArray></GeometryPathType></PathGeometry></Properties>"
+    )
+}
+
+fn text_frame(page: &Page, block: &Block, id: &str) -> String {
+    format!(
+        "<TextFrame Self=\"TextFrame_{id}\" Name=\"{}\" ParentStory=\"Story_{id}\" ContentType=\"TextType\" ItemTransform=\"1 0 0 1 0 0\">{}<TextFramePreference VerticalJustification=\"TopAlign\"/></TextFrame>",
+        xml_escape(&block.id),
+        path_geometry(page, block)
+    )
+}
+
+fn rectangle(page: &Page, block: &Block, id: &str, fill: Option<&str>) -> String {
+    let (content_type, fill) = match fill {
+        Some(swatch) => ("Unassigned", format!("Color/{}", xml_escape(swatch))),
+        None => ("GraphicType", "Swatch/None".to_string()),
+    };
+    format!(
+        "<Rectangle Self=\"Rectangle_{id}\" Name=\"{}\" ContentType=\"{content_type}\" FillColor=\"{fill}\" StrokeWeight=\"0\" ItemTransform=\"1 0 0 1 0 0\">{}</Rectangle>",
+        xml_escape(&block.id),
+        path_geometry(page, block)
+    )
+}
+
+fn container_xml() -> String {
+    format!(
+        "{XML_DECL}<container xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\" version=\"1.0\"><rootfiles><rootfile full-path=\"designmap.xml\" media-type=\"text/xml\"/></rootfiles></container>"
+    )
+}
+
+fn designmap_xml(page: &Page, spread_id: &str, stories: &[Story]) -> String {
+    let (pw, ph) = page.size.dimensions();
+    let mut xml = format!(
+        "{XML_DECL}<Document xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\" Self=\"d\">"
+    );
+    xml.push_str(&format!(
+        "<DocumentPreference PageWidth=\"{pw}\" PageHeight=\"{ph}\" FacingPages=\"false\" PagesPerDocument=\"1\"/>"
+    ));
+    for res in ["Fonts", "Styles", "Graphic"] {
+        xml.push_str(&format!("<idPkg:{res} src=\"Resources/{res}.xml\"/>"));
+    }
+    xml.push_str(&format!(
+        "<idPkg:Spread src=\"Spreads/Spread_{spread_id}.xml\"/>"
+    ));
+    for story in stories {
+        xml.push_str(&format!(
+            "<idPkg:Story src=\"Stories/Story_{}.xml\"/>",
+            story.id
+        ));
+    }
+    xml.push_str("</Document>");
+    xml
+}
+
+fn fonts_xml(styles: &BTreeMap<String, &TextStyle>) -> String {
+    let mut xml = format!(
+        "{XML_DECL}<idPkg:Fonts xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\">"
+    );
+    let mut families: Vec<&str> = styles.values().map(|s| s.font_family.as_str()).collect();
+    families.sort_unstable();
+    families.dedup();
+    for family in families {
+        let family = xml_escape(family);
+        xml.push_str(&format!(
+            "<FontFamily Self=\"FontFamily/{family}\" Name=\"{family}\"><Font Self=\"FontFamily/{family}\tRegular\" FontFamily=\"{family}\" Name=\"Regular\" PostScriptName=\"{}\"/></FontFamily>",
+            family.replace(' ', "")
+        ));
+    }
+    xml.push_str("</idPkg:Fonts>");
+    xml
+}
+
+fn styles_xml(styles: &BTreeMap<String, &TextStyle>) -> String {
+    let mut xml = format!(
+        "{XML_DECL}<idPkg:Styles xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\">"
+    );
+    xml.push_str("<RootCharacterStyleGroup Self=\"u_csg\"><CharacterStyle Self=\"CharacterStyle/$ID/[No character style]\" Name=\"$ID/[No character style]\"/></RootCharacterStyleGroup>");
+    xml.push_str("<RootParagraphStyleGroup Self=\"u_psg\">");
+    for (name, style) in styles {
+        let name = xml_escape(name);
+        let font_style = if style.weight.eq_ignore_ascii_case("bold") {
+            "Bold"
+        } else {
+            "Regular"
+        };
+        let fill = style
+            .color
+            .as_ref()
+            .map_or("Black".to_string(), swatch_name);
+        xml.push_str(&format!(
+            "<ParagraphStyle Self=\"ParagraphStyle/{name}\" Name=\"{name}\" AppliedFont=\"{}\" FontStyle=\"{font_style}\" PointSize=\"{}\" Leading=\"{}\" FillColor=\"Color/{}\"/>",
+            xml_escape(&style.font_family),
+            style.font_size,
+            style.font_size * style.line_height,
+            xml_escape(&fill)
+        ));
+    }
+    xml.push_str("</RootParagraphStyleGroup></idPkg:Styles>");
+    xml
+}
+
+fn graphic_xml(colors: &BTreeMap<String, CmykColor>) -> String {
+    let mut xml = format!(
+        "{XML_DECL}<idPkg:Graphic xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\">"
+    );
+    for (name, color) in colors {
+        let name = xml_escape(name);
+        xml.push_str(&format!(
+            "<Color Self=\"Color/{name}\" Name=\"{name}\" Model=\"Process\" Space=\"CMYK\" ColorValue=\"{:.1} {:.1} {:.1} {:.1}\"/>",
+            color.c, color.m, color.y, color.k
+        ));
+    }
+    xml.push_str("<Swatch Self=\"Swatch/None\" Name=\"None\"/>");
+    xml.push_str("<ObjectStyle Self=\"ObjectStyle/$ID/[None]\" Name=\"$ID/[None]\"/>");
+    xml.push_str("</idPkg:Graphic>");
+    xml
+}
+
+fn spread_xml(page: &Page, spread_id: &str, items: &str) -> String {
+    let (pw, ph) = page.size.dimensions();
+    let m = page.grid.effective_margin();
+    format!(
+        "{XML_DECL}<idPkg:Spread xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\"><Spread Self=\"Spread_{spread_id}\" PageCount=\"1\" ItemTransform=\"1 0 0 1 0 0\"><Page Self=\"Page_{spread_id}\" Name=\"{}\" GeometricBounds=\"0 0 {ph} {pw}\" ItemTransform=\"1 0 0 1 0 0\"><MarginPreference ColumnCount=\"{}\" ColumnGutter=\"{}\" Top=\"{}\" Bottom=\"{}\" Left=\"{}\" Right=\"{}\"/></Page>{items}</Spread></idPkg:Spread>",
+        page.number, page.grid.columns, page.grid.gutter_h, m.top, m.bottom, m.left, m.right
+    )
+}
+
+fn story_xml(story: &Story) -> String {
+    let mut xml = format!(
+        "{XML_DECL}<idPkg:Story xmlns:idPkg=\"{PACKAGING_NS}\" DOMVersion=\"{DOM_VERSION}\"><Story Self=\"Story_{}\" AppliedTOCStyle=\"n\" TrackChanges=\"false\">",
+        story.id
+    );
+    let style = xml_escape(&story.style);
+    let last = story.paragraphs.len().saturating_sub(1);
+    for (i, paragraph) in story.paragraphs.iter().enumerate() {
+        // Paragraphs end with <Br/>, except the last so the frame doesn't
+        // carry a trailing empty line.
+        let br = if i < last { "<Br/>" } else { "" };
+        xml.push_str(&format!(
+            "<ParagraphStyleRange AppliedParagraphStyle=\"ParagraphStyle/{style}\"><CharacterStyleRange AppliedCharacterStyle=\"CharacterStyle/$ID/[No character style]\"><Content>{}</Content>{br}</CharacterStyleRange></ParagraphStyleRange>",
+            xml_escape(paragraph)
+        ));
+    }
+    xml.push_str("</Story></idPkg:Story>");
+    xml
+}
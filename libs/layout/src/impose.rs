@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::LayoutError;
+use crate::grid::Rect;
+use crate::page::{PageSize, MARK_AREA};
+use crate::render::{page_to_svg, render_trim_marks};
+use crate::sheet::Sheet;
+
+/// How items are ganged up on a press sheet.
+#[derive(Debug, Clone, Copy)]
+pub struct Imposition {
+    pub stock: PageSize,
+    /// Bleed around each item, in points. Neighbouring items are spaced so
+    /// their bleeds don't overlap.
+    pub bleed: f64,
+}
+
+/// The two sides of an imposed press sheet, as SVG.
+#[derive(Debug, Clone)]
+pub struct ImposedSheet {
+    pub front: String,
+    pub back: String,
+}
+
+impl ImposedSheet {
+    /// Write `front.svg` and `back.svg` into `dir`.
+    pub fn write(&self, dir: &Path) -> Result<(PathBuf, PathBuf), LayoutError> {
+        std::fs::create_dir_all(dir)?;
+        let front = dir.join("front.svg");
+        let back = dir.join("back.svg");
+        std::fs::write(&front, &self.front)?;
+        std::fs::write(&back, &self.back)?;
+        Ok((front, back))
+    }
+}
+
+impl Imposition {
+    /// How many `item_w` x `item_h` items fit across and down the stock,
+    /// each with its bleed, leaving room for crop marks around the block.
+    /// An item with no size fits zero times rather than endlessly.
+    pub fn fit(&self, item_w: f64, item_h: f64) -> (u32, u32) {
+        let (sw, sh) = self.stock.dimensions();
+        let reserve = 2.0 * MARK_AREA;
+        let fit = |avail: f64, pitch: f64| {
+            if pitch > 0.0 {
+                ((avail - reserve) / pitch).floor().max(0.0) as u32
+            } else {
+                0
+            }
+        };
+        (
+            fit(sw, item_w + 2.0 * self.bleed),
+            fit(sh, item_h + 2.0 * self.bleed),
+        )
+    }
+
+    /// Trim rects of every item that fits on the stock, row-major and
+    /// centered, leaving room for bleed and crop marks around the block.
+    pub fn slots(&self, item_w: f64, item_h: f64) -> Vec<Rect> {
+        let (sw, sh) = self.stock.dimensions();
+        let pitch_w = item_w + 2.0 * self.bleed;
+        let pitch_h = item_h + 2.0 * self.bleed;
+        let (columns, rows) = self.fit(item_w, item_h);
+
+        let block_w = columns as f64 * pitch_w - 2.0 * self.bleed;
+        let block_h = rows as f64 * pitch_h - 2.0 * self.bleed;
+        let (ox, oy) = ((sw - block_w) / 2.0, (sh - block_h) / 2.0);
+        (0..rows)
+            .flat_map(|row| (0..columns).map(move |col| (col, row)))
+            .map(|(col, row)| {
+                let x = ox + col as f64 * pitch_w;
+                let y = oy + row as f64 * pitch_h;
+                Rect::new(x, y, item_w, item_h)
+            })
+            .collect()
+    }
+
+    /// Slots for the back of the sheet: the front's slots mirrored across
+    /// the stock's vertical center, so each back lands behind its front
+    /// once the sheet is turned over.
+    pub fn back_slots(&self, item_w: f64, item_h: f64) -> Vec<Rect> {
+        let (sw, _) = self.stock.dimensions();
+        self.slots(item_w, item_h)
+            .into_iter()
+            .map(|r| Rect::new(sw - r.x - r.w, r.y, r.w, r.h))
+            .collect()
+    }
+
+    /// Gang `sheet` up on the stock: the front page in every slot on one
+    /// side, the mirrored back page in the mirrored slots on the other, with
+    /// crop marks that register on both sides.
+    pub fn impose(&self, sheet: &Sheet) -> ImposedSheet {
+        let (item_w, item_h) = sheet.front.size.dimensions();
+        let front_svg = page_to_svg(&sheet.front);
+        let back_svg = page_to_svg(&sheet.mirrored_back());
+        ImposedSheet {
+            front: self.side_svg(&front_svg, &self.slots(item_w, item_h)),
+            back: self.side_svg(&back_svg, &self.back_slots(item_w, item_h)),
+        }
+    }
+
+    fn side_svg(&self, item_svg: &str, slots: &[Rect]) -> String {
+        let (sw, sh) = self.stock.dimensions();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {sw} {sh}\" width=\"{sw}\" height=\"{sh}\">"
+        );
+        svg.push_str(&format!(
+            "<rect width=\"{sw}\" height=\"{sh}\" fill=\"white\"/>"
+        ));
+        for slot in slots {
+            // Nest the item's own <svg> root, positioned at the slot.
+            let placed = format!("<svg x=\"{}\" y=\"{}\" ", slot.x, slot.y);
+            svg.push_str(&item_svg.replacen("<svg ", &placed, 1));
+        }
+
+        render_trim_marks(&mut svg, slots, self.bleed);
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_slots_register_with_the_front() {
+        let imposition = Imposition {
+            stock: PageSize::Tabloid,
+            bleed: 9.0,
+        };
+        let (item_w, item_h) = (221.0, 188.0);
+        let (columns, _) = imposition.fit(item_w, item_h);
+        assert!(columns > 1);
+        let front = imposition.slots(item_w, item_h);
+        let back = imposition.back_slots(item_w, item_h);
+        let (sw, _) = imposition.stock.dimensions();
+
+        let last_in_row = front[columns as usize - 1];
+        assert!((back[0].x - last_in_row.x).abs() < 1e-9);
+        assert!((back[0].x + back[0].w / 2.0 - (sw - front[0].x - front[0].w / 2.0)).abs() < 1e-9);
+        assert_eq!(back[0].y, front[0].y);
+    }
+
+    #[test]
+    fn zero_sized_item_fits_nowhere() {
+        let imposition = Imposition {
+            stock: PageSize::Tabloid,
+            bleed: 0.0,
+        };
+        assert_eq!(imposition.fit(0.0, 0.0), (0, 0));
+        assert!(imposition.slots(0.0, 0.0).is_empty());
+    }
+}
@@ -1,8 +1,22 @@
+pub mod color;
+pub mod contact_sheet;
+pub mod document;
+pub mod error;
 pub mod grid;
+#[cfg(feature = "idml")]
+pub mod idml;
+pub mod impose;
 pub mod page;
+pub mod planner;
+pub mod preflight;
 pub mod provenance;
 pub mod render;
+pub mod sheet;
+pub mod units;
+pub mod zine;
 
+pub use document::Document;
+pub use error::LayoutError;
 pub use grid::Grid;
 pub use page::Page;
 pub use provenance::DecisionTree;
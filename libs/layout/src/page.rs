@@ -1,13 +1,41 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
-use crate::grid::Grid;
+use crate::color::{CmykColor, ColorPalette};
+use crate::error::LayoutError;
+use crate::grid::{Binding, Grid, Rect};
+use crate::preflight::{PreflightOptions, PreflightReport, Severity};
+use crate::render::{place_text, wrap_text, TEXT_INSET};
+use crate::units::{in_to_pt, mm_to_pt, pt_to_in, pt_to_mm, Unit};
+
+/// Default safe-zone inset from the trim edge: 0.125in in points.
+pub const DEFAULT_SAFE_INSET: f64 = 9.0;
+
+/// Room outside the bleed reserved for printer's marks: 0.25in in points.
+pub const MARK_AREA: f64 = 18.0;
+
+/// The standard print boxes of a page, in page coordinates (trim at origin).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PageBoxes {
+    pub trim: Rect,
+    pub bleed: Rect,
+    pub media: Rect,
+}
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum PageSize {
+    A3,
     A4,
     A5,
+    /// ISO B5, 176 x 250 mm.
+    B5,
     Letter,
     HalfLetter,
+    Legal,
+    /// US Tabloid (Ledger in landscape), 11 x 17 in.
+    Tabloid,
     Custom { width: f64, height: f64 },
 }
 
@@ -15,13 +43,116 @@ impl PageSize {
     /// Returns (width, height) in points (1pt = 1/72 inch).
     pub fn dimensions(&self) -> (f64, f64) {
         match self {
+            PageSize::A3 => (841.89, 1190.55),
             PageSize::A4 => (595.28, 841.89),
             PageSize::A5 => (419.53, 595.28),
+            PageSize::B5 => (498.9, 708.66),
             PageSize::Letter => (612.0, 792.0),
             PageSize::HalfLetter => (396.0, 612.0),
+            PageSize::Legal => (612.0, 1008.0),
+            PageSize::Tabloid => (792.0, 1224.0),
             PageSize::Custom { width, height } => (*width, *height),
         }
     }
+
+    /// Returns (width, height) in millimeters.
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        let (w, h) = self.dimensions();
+        (pt_to_mm(w), pt_to_mm(h))
+    }
+
+    /// Returns (width, height) in inches.
+    pub fn dimensions_inches(&self) -> (f64, f64) {
+        let (w, h) = self.dimensions();
+        (pt_to_in(w), pt_to_in(h))
+    }
+
+    pub fn from_inches(width: f64, height: f64) -> Self {
+        PageSize::Custom {
+            width: in_to_pt(width),
+            height: in_to_pt(height),
+        }
+    }
+
+    pub fn from_mm(width: f64, height: f64) -> Self {
+        PageSize::Custom {
+            width: mm_to_pt(width),
+            height: mm_to_pt(height),
+        }
+    }
+}
+
+/// Why a string couldn't be read as a [`PageSize`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageSizeParseError {
+    /// Not a known size name, and not shaped like `WxH<unit>`.
+    UnknownName(String),
+    /// Dimensions without a trailing `mm`, `in`, or `pt`.
+    MissingUnit(String),
+    /// A unit was given but the width or height isn't a positive number.
+    BadDimensions(String),
+}
+
+impl std::fmt::Display for PageSizeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownName(s) => write!(
+                f,
+                "unknown page size `{s}`; use a name like `A4` or dimensions like `8.5x11in`"
+            ),
+            Self::MissingUnit(s) => {
+                write!(f, "page size `{s}` needs a unit: `mm`, `in`, or `pt`")
+            }
+            Self::BadDimensions(s) => {
+                write!(f, "page size `{s}` needs a positive width and height")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PageSizeParseError {}
+
+impl std::str::FromStr for PageSize {
+    type Err = PageSizeParseError;
+
+    /// Accepts size names case-insensitively (`a4`, `LETTER`, `half-letter`)
+    /// or custom dimensions such as `210x297mm`, `8.5x11in`, `612x792pt`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().to_lowercase();
+        let named = match input.as_str() {
+            "a3" => Some(PageSize::A3),
+            "a4" => Some(PageSize::A4),
+            "a5" => Some(PageSize::A5),
+            "b5" => Some(PageSize::B5),
+            "letter" => Some(PageSize::Letter),
+            "half-letter" | "halfletter" | "half_letter" => Some(PageSize::HalfLetter),
+            "legal" => Some(PageSize::Legal),
+            "tabloid" => Some(PageSize::Tabloid),
+            _ => None,
+        };
+        if let Some(size) = named {
+            return Ok(size);
+        }
+
+        let Some((w, rest)) = input.split_once('x') else {
+            return Err(PageSizeParseError::UnknownName(s.to_string()));
+        };
+        let unit = [Unit::Mm, Unit::In, Unit::Pt]
+            .into_iter()
+            .find(|u| rest.ends_with(u.suffix()));
+        let Some(unit) = unit else {
+            return Err(PageSizeParseError::MissingUnit(s.to_string()));
+        };
+        let h = &rest[..rest.len() - unit.suffix().len()];
+        let parse = |v: &str| v.trim().parse::<f64>().ok().filter(|v| *v > 0.0);
+        match (parse(w), parse(h)) {
+            (Some(w), Some(h)) => Ok(PageSize::Custom {
+                width: unit.to_pt(w),
+                height: unit.to_pt(h),
+            }),
+            _ => Err(PageSizeParseError::BadDimensions(s.to_string())),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +161,50 @@ pub struct Page {
     pub size: PageSize,
     pub grid: Grid,
     pub blocks: Vec<Block>,
+    /// Running header/footer, drawn in the margins apart from content blocks.
+    #[serde(default)]
+    pub chrome: Option<PageChrome>,
+    /// Named text styles that text blocks can refer to by `style_ref`.
+    #[serde(default)]
+    pub styles: BTreeMap<String, TextStyle>,
+    /// Named colors that fills can refer to by `color_ref`.
+    #[serde(default)]
+    pub palette: ColorPalette,
+    /// Trim boxes of items ganged onto this page as a press sheet; the SVG
+    /// and LaTeX renderers draw crop marks for them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim_marks: Option<TrimMarks>,
+}
+
+/// Where a press sheet gets cut: one trim rect per item, each surrounded by
+/// `bleed` points of printed bleed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrimMarks {
+    pub trims: Vec<Rect>,
+    pub bleed: f64,
+}
+
+/// Running header and footer templates. `{page}` expands to the page number.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageChrome {
+    #[serde(default)]
+    pub header: Option<String>,
+    #[serde(default)]
+    pub footer: Option<String>,
+}
+
+impl PageChrome {
+    pub fn header_text(&self, page: u32) -> Option<String> {
+        self.header.as_ref().map(|t| expand_page_token(t, page))
+    }
+
+    pub fn footer_text(&self, page: u32) -> Option<String> {
+        self.footer.as_ref().map(|t| expand_page_token(t, page))
+    }
+}
+
+fn expand_page_token(template: &str, page: u32) -> String {
+    template.replace("{page}", &page.to_string())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,13 +217,104 @@ pub struct Block {
     pub content: BlockContent,
     /// Links this block to a decision in the provenance tree.
     pub decision_ids: Vec<String>,
+    /// Clip rendered content to the block rect.
+    #[serde(default)]
+    pub clip: bool,
+    /// Corner radius of the block rect in points; 0 draws square corners.
+    #[serde(default)]
+    pub corner_radius: f64,
+    /// Clockwise rotation in degrees about the block's center. Only the
+    /// drawn orientation changes; the block keeps its grid cells.
+    #[serde(default)]
+    pub rotation: f64,
+}
+
+/// Why [`Page::try_add_block`] refused a block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockError {
+    /// The block spans no columns or no rows.
+    ZeroSpan { block_id: String },
+    /// The block starts at `start` and spans `span` along `axis` ("column"
+    /// or "row"), past the grid's `limit` cells on that axis.
+    OutOfBounds {
+        block_id: String,
+        axis: &'static str,
+        start: u32,
+        span: u32,
+        limit: u32,
+    },
+}
+
+impl std::fmt::Display for BlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroSpan { block_id } => {
+                write!(f, "block `{block_id}` must span at least one column and row")
+            }
+            Self::OutOfBounds {
+                block_id,
+                axis,
+                start,
+                span,
+                limit,
+            } => write!(
+                f,
+                "block `{block_id}` at {axis} {start} spans {span} but the grid has {limit} {axis}s"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlockError {}
+
+/// What [`Page::flow_text`] placed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextFlow {
+    /// Ids of the blocks created, in flow order.
+    pub block_ids: Vec<String>,
+    /// Text that didn't fit before the grid ran out of free cells; empty
+    /// when it all fit.
+    pub remainder: String,
+}
+
+/// Split `text` after its first `words` words, trimming the whitespace at
+/// the break.
+fn split_after_words(text: &str, words: usize) -> (&str, &str) {
+    let mut end = 0;
+    for word in text.split_whitespace().take(words) {
+        end += text[end..].find(word).unwrap_or(0) + word.len();
+    }
+    (text[..end].trim_end(), text[end..].trim_start())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum BlockContent {
-    Text { body: String, style: TextStyle },
+    Text {
+        body: String,
+        style: TextStyle,
+        /// Name of a style in [`Page::styles`]; when it resolves, it is
+        /// used instead of the inline `style`.
+        #[serde(default)]
+        style_ref: Option<String>,
+    },
     Image { path: String, alt: String },
+    /// A QR code encoding `data`, drawn as a square matrix sized to the block.
+    Qr { data: String },
+    /// A simple table; columns share the block width equally.
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+        style: TextStyle,
+    },
+    /// A solid rectangle of `color` filling the block.
+    Fill {
+        color: CmykColor,
+        /// Name of a color in [`Page::palette`]; when it resolves, it is
+        /// used instead of the inline `color`.
+        #[serde(default)]
+        color_ref: Option<String>,
+    },
     Empty,
 }
 
@@ -58,6 +324,48 @@ pub struct TextStyle {
     pub font_family: String,
     pub line_height: f64,
     pub weight: String,
+    /// Text color; previews fall back to near-black when unset.
+    #[serde(default)]
+    pub color: Option<CmykColor>,
+    #[serde(default)]
+    pub align: TextAlign,
+    #[serde(default)]
+    pub valign: VAlign,
+}
+
+/// Horizontal alignment of a text block's lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch every line but the last to the block width. Renderers treat
+    /// this as best-effort.
+    Justify,
+}
+
+impl TextAlign {
+    /// The lowercase name, as CSS `text-align` and TikZ `align=` spell it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justify => "justify",
+        }
+    }
+}
+
+/// Where a text block's lines sit between its top and bottom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
 }
 
 impl Default for TextStyle {
@@ -67,6 +375,9 @@ impl Default for TextStyle {
             font_family: "Helvetica".to_string(),
             line_height: 1.4,
             weight: "normal".to_string(),
+            color: None,
+            align: TextAlign::Left,
+            valign: VAlign::Top,
         }
     }
 }
@@ -79,10 +390,599 @@ impl Page {
             size,
             grid: Grid::new(columns, rows, w, h),
             blocks: Vec::new(),
+            chrome: None,
+            styles: BTreeMap::new(),
+            palette: ColorPalette::new(),
+            trim_marks: None,
         }
     }
 
+    /// A page laid out on `grid`. A grid bound into a spread (any binding
+    /// but [`Binding::None`]) takes its side from `number`, so even pages
+    /// get mirrored margins without the caller asking.
+    pub fn with_grid(number: u32, size: PageSize, grid: Grid) -> Self {
+        let mut page = Self {
+            number,
+            size,
+            grid,
+            blocks: Vec::new(),
+            chrome: None,
+            styles: BTreeMap::new(),
+            palette: ColorPalette::new(),
+            trim_marks: None,
+        };
+        page.bind_if_facing();
+        page
+    }
+
     pub fn add_block(&mut self, block: Block) {
         self.blocks.push(block);
     }
+
+    /// Like [`Page::add_block`], but refuses blocks with a zero span or that
+    /// run past the grid.
+    pub fn try_add_block(&mut self, block: Block) -> Result<(), BlockError> {
+        let g = &self.grid;
+        if block.col_span == 0 || block.row_span == 0 {
+            return Err(BlockError::ZeroSpan { block_id: block.id });
+        }
+        let checks = [
+            ("column", block.col, block.col_span, g.columns),
+            ("row", block.row, block.row_span, g.rows),
+        ];
+        for (axis, start, span, limit) in checks {
+            if start.saturating_add(span) > limit {
+                return Err(BlockError::OutOfBounds {
+                    block_id: block.id,
+                    axis,
+                    start,
+                    span,
+                    limit,
+                });
+            }
+        }
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    /// The style a text block is set in: the named page style `style_ref`
+    /// when there is one, otherwise the block's inline `style`.
+    pub fn resolve_style<'a>(
+        &'a self,
+        style: &'a TextStyle,
+        style_ref: Option<&str>,
+    ) -> &'a TextStyle {
+        style_ref
+            .and_then(|name| self.styles.get(name))
+            .unwrap_or(style)
+    }
+
+    /// The color a fill is painted in: the palette color `color_ref` when
+    /// there is one, otherwise the block's inline `color`.
+    pub fn resolve_color<'a>(
+        &'a self,
+        color: &'a CmykColor,
+        color_ref: Option<&str>,
+    ) -> &'a CmykColor {
+        color_ref
+            .and_then(|name| self.palette.get(name))
+            .unwrap_or(color)
+    }
+
+    /// Returns the page's (width, height) in millimeters.
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        self.size.dimensions_mm()
+    }
+
+    /// Returns the page's (width, height) in inches.
+    pub fn dimensions_inches(&self) -> (f64, f64) {
+        self.size.dimensions_inches()
+    }
+
+    /// Blocks top to bottom, then left to right, by their starting cell.
+    /// Blocks starting in the same cell keep insertion order.
+    pub fn blocks_in_reading_order(&self) -> Vec<&Block> {
+        let mut blocks: Vec<&Block> = self.blocks.iter().collect();
+        blocks.sort_by_key(|b| (b.row, b.col));
+        blocks
+    }
+
+    /// Change the page size, rebuilding the grid with the same columns, rows,
+    /// margins, gutters, binding, and baseline. Column and row tracks are scaled to the new
+    /// content area, keeping their proportions. Blocks are cell-relative, so
+    /// they keep their placement. A grid with no columns or rows, or whose
+    /// tracks add up to nothing, has no proportions to keep; the page is left
+    /// as it was.
+    pub fn resize(&mut self, new_size: PageSize) -> Result<(), LayoutError> {
+        let (w, h) = new_size.dimensions();
+        let g = &self.grid;
+        if g.columns == 0 || g.rows == 0 {
+            return Err(LayoutError::InvalidGrid(format!(
+                "{} x {} grid has no cells to resize",
+                g.columns, g.rows
+            )));
+        }
+        let empty = |tracks: &[f64]| !tracks.is_empty() && tracks.iter().sum::<f64>() <= 0.0;
+        if empty(&g.column_tracks) || empty(&g.row_tracks) {
+            return Err(LayoutError::InvalidGrid(
+                "tracks add up to no width or height to scale".to_string(),
+            ));
+        }
+        let mut grid = Grid::with_gutter(g.columns, g.rows, w, h, g.margin.clone(), g.gutter_h)
+            .with_binding(g.binding);
+        grid.baseline = g.baseline;
+        grid.gutter_v = g.gutter_v;
+        grid.row_height = (h - g.margin.top - g.margin.bottom
+            - g.gutter_v * g.rows.saturating_sub(1) as f64)
+            / g.rows as f64;
+        let scale = |tracks: &[f64], available: f64, gutter: f64| -> Vec<f64> {
+            let total: f64 = tracks.iter().sum();
+            let room = available - gutter * tracks.len().saturating_sub(1) as f64;
+            tracks.iter().map(|t| t * room / total).collect()
+        };
+        let m = &g.margin;
+        let narrowest = |tracks: &[f64]| tracks.iter().copied().fold(f64::INFINITY, f64::min);
+        if !g.column_tracks.is_empty() {
+            grid.column_tracks = scale(&g.column_tracks, w - m.left - m.right, g.gutter_h);
+            grid.column_width = narrowest(&grid.column_tracks);
+        }
+        if !g.row_tracks.is_empty() {
+            grid.row_tracks = scale(&g.row_tracks, h - m.top - m.bottom, g.gutter_v);
+            grid.row_height = narrowest(&grid.row_tracks);
+        }
+        self.grid = grid;
+        self.size = new_size;
+        Ok(())
+    }
+
+    /// Which side of a spread this page falls on by its number: odd pages
+    /// are recto, even pages verso.
+    pub fn facing_side(&self) -> Binding {
+        if self.number % 2 == 1 {
+            Binding::Recto
+        } else {
+            Binding::Verso
+        }
+    }
+
+    /// Bind the page into a spread by its number, so even pages print with
+    /// mirrored margins.
+    pub fn bind_facing(&mut self) {
+        self.grid.binding = self.facing_side();
+    }
+
+    /// [`Page::bind_facing`], if the grid is already part of a spread.
+    pub(crate) fn bind_if_facing(&mut self) {
+        if self.grid.binding != Binding::None {
+            self.bind_facing();
+        }
+    }
+
+    /// Trim box (the page itself), bleed box (trim grown by `bleed`), and
+    /// media box (bleed plus room for crop marks).
+    pub fn boxes(&self, bleed: f64) -> PageBoxes {
+        let (pw, ph) = self.size.dimensions();
+        let trim = Rect::new(0.0, 0.0, pw, ph);
+        let bleed = trim.expand(bleed);
+        PageBoxes {
+            trim,
+            bleed,
+            media: bleed.expand(MARK_AREA),
+        }
+    }
+
+    /// Ids of blocks that cross into the border `inset` points inside the trim
+    /// edge, where critical content risks being cut.
+    pub fn safe_zone_violations(&self, inset: f64) -> Vec<String> {
+        let (pw, ph) = self.size.dimensions();
+        self.blocks
+            .iter()
+            .filter(|b| {
+                let Rect { x, y, w, h } = self.grid.cell_rect(b.col, b.row, b.col_span, b.row_span);
+                x < inset || y < inset || x + w > pw - inset || y + h > ph - inset
+            })
+            .map(|b| b.id.clone())
+            .collect()
+    }
+
+    /// `(block_id, path)` for every image block whose file does not exist.
+    /// Relative paths resolve against `base_dir`.
+    pub fn missing_image_assets(&self, base_dir: &Path) -> Vec<(String, String)> {
+        self.blocks
+            .iter()
+            .filter_map(|b| match &b.content {
+                BlockContent::Image { path, .. } if !base_dir.join(path).is_file() => {
+                    Some((b.id.clone(), path.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Pairs of block ids whose cell spans overlap, in block order.
+    pub fn overlapping_blocks(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for (i, a) in self.blocks.iter().enumerate() {
+            for b in &self.blocks[i + 1..] {
+                let cols = a.col < b.col.saturating_add(b.col_span)
+                    && b.col < a.col.saturating_add(a.col_span);
+                let rows = a.row < b.row.saturating_add(b.row_span)
+                    && b.row < a.row.saturating_add(a.row_span);
+                if cols && rows {
+                    pairs.push((a.id.clone(), b.id.clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Pour `body` into text blocks, one per free grid cell, starting at
+    /// `(start_col, start_row)` and running down each column before moving
+    /// right. Each block takes as many lines as fit its cell at `style`'s
+    /// size, measured the way the renderers wrap text. Cells already covered
+    /// by a block are skipped.
+    pub fn flow_text(
+        &mut self,
+        body: &str,
+        style: TextStyle,
+        start_col: u32,
+        start_row: u32,
+    ) -> TextFlow {
+        let mut block_ids = Vec::new();
+        let mut rest = body.trim();
+        let mut next_id = 1;
+        let cells: Vec<(u32, u32)> = (start_col..self.grid.columns)
+            .flat_map(|col| {
+                let first = if col == start_col { start_row } else { 0 };
+                (first..self.grid.rows).map(move |row| (col, row))
+            })
+            .collect();
+        for (col, row) in cells {
+            if rest.is_empty() {
+                break;
+            }
+            let covered = self.blocks.iter().any(|b| {
+                (b.col..b.col.saturating_add(b.col_span)).contains(&col)
+                    && (b.row..b.row.saturating_add(b.row_span)).contains(&row)
+            });
+            let rect = self.grid.cell_rect(col, row, 1, 1);
+            let placement = place_text(&self.grid, rect, &style);
+            if covered || placement.first_baseline > rect.y + rect.h {
+                continue;
+            }
+            let lines = wrap_text(rest, rect.w - 2.0 * TEXT_INSET, style.font_size);
+            let words = lines
+                .iter()
+                .take(placement.fit)
+                .map(|l| l.split_whitespace().count())
+                .sum();
+            let (placed, remainder) = split_after_words(rest, words);
+            rest = remainder;
+
+            while self.blocks.iter().any(|b| b.id == format!("flow-{next_id}")) {
+                next_id += 1;
+            }
+            let id = format!("flow-{next_id}");
+            next_id += 1;
+            block_ids.push(id.clone());
+            self.add_block(Block {
+                id,
+                col,
+                row,
+                col_span: 1,
+                row_span: 1,
+                content: BlockContent::Text {
+                    body: placed.to_string(),
+                    style: style.clone(),
+                    style_ref: None,
+                },
+                decision_ids: Vec::new(),
+                clip: false,
+                corner_radius: 0.0,
+                rotation: 0.0,
+            });
+        }
+        TextFlow {
+            block_ids,
+            remainder: rest.to_string(),
+        }
+    }
+
+    /// Grid cells no block covers, row by row.
+    pub fn empty_cells(&self) -> Vec<(u32, u32)> {
+        self.grid
+            .cells()
+            .filter(|&(col, row)| {
+                !self.blocks.iter().any(|b| {
+                    (b.col..b.col.saturating_add(b.col_span)).contains(&col)
+                        && (b.row..b.row.saturating_add(b.row_span)).contains(&row)
+                })
+            })
+            .collect()
+    }
+
+    /// Ids of blocks whose rect runs into the margins, as a grid whose
+    /// tracks or gutters outgrow the content area can make them do.
+    pub fn margin_violations(&self) -> Vec<String> {
+        const EPSILON: f64 = 1e-6;
+        let (pw, ph) = self.size.dimensions();
+        let m = self.grid.effective_margin();
+        self.blocks
+            .iter()
+            .filter(|b| {
+                let Rect { x, y, w, h } = self.grid.cell_rect(b.col, b.row, b.col_span, b.row_span);
+                x < m.left - EPSILON
+                    || y < m.top - EPSILON
+                    || x + w > pw - m.right + EPSILON
+                    || y + h > ph - m.bottom + EPSILON
+            })
+            .map(|b| b.id.clone())
+            .collect()
+    }
+
+    /// Ids of text blocks set smaller than `min_font_size` points.
+    pub fn legibility_warnings(&self, min_font_size: f64) -> Vec<String> {
+        self.blocks
+            .iter()
+            .filter(|b| match &b.content {
+                BlockContent::Text {
+                    style, style_ref, ..
+                } => self.resolve_style(style, style_ref.as_deref()).font_size < min_font_size,
+                BlockContent::Table { style, .. } => style.font_size < min_font_size,
+                _ => false,
+            })
+            .map(|b| b.id.clone())
+            .collect()
+    }
+
+    /// Run the checks enabled in `opts` and collect what they find.
+    pub fn preflight(&self, opts: &PreflightOptions) -> PreflightReport {
+        let mut report = PreflightReport::default();
+        let g = &self.grid;
+        for b in &self.blocks {
+            if !g.contains(b.col, b.row, b.col_span, b.row_span) {
+                report.push(Severity::Error, &b.id, "falls outside the grid".to_string());
+            }
+        }
+        for (a, b) in self.overlapping_blocks() {
+            report.push(Severity::Error, &a, format!("overlaps block `{b}`"));
+        }
+        for id in self.margin_violations() {
+            report.push(Severity::Warning, &id, "runs into the margin".to_string());
+        }
+        if opts.empty_cells {
+            for (col, row) in self.empty_cells() {
+                let message = format!("cell ({col}, {row}) is empty");
+                report.push(Severity::Warning, "", message);
+            }
+        }
+        let min = opts.min_font_size;
+        for id in self.legibility_warnings(min) {
+            let message = format!("text is smaller than {min}pt");
+            report.push(Severity::Warning, &id, message);
+        }
+        if let Some(inset) = opts.safe_inset {
+            for id in self.safe_zone_violations(inset) {
+                let message = format!("crosses the {inset}pt safe zone");
+                report.push(Severity::Warning, &id, message);
+            }
+        }
+        if let Some(dir) = &opts.asset_dir {
+            for (id, path) in self.missing_image_assets(dir) {
+                report.push(Severity::Error, &id, format!("image `{path}` not found"));
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Margin;
+
+    fn empty_block(id: &str, col: u32, row: u32) -> Block {
+        Block {
+            id: id.to_string(),
+            col,
+            row,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Empty,
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    #[test]
+    fn resize_letter_to_a4_keeps_cells_and_gutters() {
+        let mut page = Page::new(1, PageSize::Letter, 3, 4);
+        page.grid = Grid::with_gutter(3, 4, 612.0, 792.0, Margin::default(), 18.0);
+        page.grid.gutter_v = 6.0;
+        page.add_block(empty_block("b", 2, 3));
+        let before = page.grid.cell_origin(2, 3);
+
+        page.resize(PageSize::A4).unwrap();
+
+        let block = &page.blocks[0];
+        assert_eq!((block.col, block.row), (2, 3));
+        assert_eq!(page.grid.gutter_h, 18.0);
+        assert_eq!(page.grid.gutter_v, 6.0);
+        assert_ne!(page.grid.cell_origin(2, 3), before);
+        // The last cell still ends at the bottom-right margin.
+        let (w, h) = PageSize::A4.dimensions();
+        let last = page.grid.cell_rect(2, 3, 1, 1);
+        assert!((last.x + last.w - (w - 36.0)).abs() < 1e-9);
+        assert!((last.y + last.h - (h - 36.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn block_touching_the_trim_crosses_the_safe_zone() {
+        let mut page = Page::new(1, PageSize::Letter, 3, 3);
+        let (w, h) = PageSize::Letter.dimensions();
+        let flush = Margin {
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+            left: 0.0,
+        };
+        page.grid = Grid::with_margin(3, 3, w, h, flush);
+        page.add_block(empty_block("edge", 0, 1));
+        page.add_block(empty_block("center", 1, 1));
+        assert_eq!(page.safe_zone_violations(in_to_pt(0.125)), ["edge"]);
+        assert_eq!(DEFAULT_SAFE_INSET, in_to_pt(0.125));
+    }
+
+    #[test]
+    fn resize_refuses_grids_without_proportions() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        page.grid.rows = 0;
+        assert!(matches!(page.resize(PageSize::A4), Err(LayoutError::InvalidGrid(_))));
+
+        page.grid = Grid::with_tracks(vec![0.0, 0.0], vec![100.0], 12.0, Margin::default());
+        assert!(matches!(page.resize(PageSize::A4), Err(LayoutError::InvalidGrid(_))));
+        assert!(matches!(page.size, PageSize::Letter));
+        assert_eq!(page.grid.column_tracks, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn facing_grid_mirrors_even_pages() {
+        let margin = Margin {
+            left: 54.0,
+            right: 36.0,
+            ..Margin::default()
+        };
+        let (w, h) = PageSize::Letter.dimensions();
+        let grid = Grid::with_margin(2, 2, w, h, margin).with_binding(Binding::Recto);
+
+        let odd = Page::with_grid(3, PageSize::Letter, grid.clone());
+        assert_eq!(odd.grid.binding, Binding::Recto);
+        assert_eq!(odd.grid.cell_origin(0, 0).0, 54.0);
+
+        let even = Page::with_grid(4, PageSize::Letter, grid.clone());
+        assert_eq!(even.grid.binding, Binding::Verso);
+        assert_eq!(even.grid.cell_origin(0, 0).0, 36.0);
+
+        let unbound = Grid::with_margin(2, 2, w, h, Margin::default());
+        let single = Page::with_grid(4, PageSize::Letter, unbound);
+        assert_eq!(single.grid.binding, Binding::None);
+    }
+
+    #[test]
+    fn overlap_check_survives_huge_spans() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        let mut wide = empty_block("wide", 1, 0);
+        wide.col_span = u32::MAX;
+        wide.row_span = u32::MAX;
+        page.add_block(wide);
+        page.add_block(empty_block("corner", 1, 1));
+        page.add_block(empty_block("left", 0, 1));
+        assert_eq!(
+            page.overlapping_blocks(),
+            [("wide".to_string(), "corner".to_string())]
+        );
+    }
+
+    #[test]
+    fn preflight_survives_huge_spans() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        let mut wide = empty_block("wide", 1, 0);
+        wide.col_span = u32::MAX;
+        wide.row_span = u32::MAX;
+        page.add_block(wide);
+        let report = page.preflight(&PreflightOptions::default());
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.severity == Severity::Error && i.block_id == "wide"));
+    }
+
+    #[test]
+    fn preflight_reports_each_check_at_its_severity() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        page.add_block(empty_block("a", 0, 0));
+        page.add_block(empty_block("b", 0, 0));
+        let mut tiny = empty_block("tiny", 1, 0);
+        tiny.content = BlockContent::Text {
+            body: "fine print".to_string(),
+            style: TextStyle {
+                font_size: 4.0,
+                ..TextStyle::default()
+            },
+            style_ref: None,
+        };
+        page.add_block(tiny);
+
+        let opts = PreflightOptions {
+            safe_inset: None,
+            ..PreflightOptions::default()
+        };
+        let report = page.preflight(&opts);
+        let found = |severity, id: &str| {
+            report
+                .issues
+                .iter()
+                .filter(|i| i.severity == severity && i.block_id == id)
+                .count()
+        };
+        assert_eq!(found(Severity::Error, "a"), 1);
+        assert_eq!(found(Severity::Warning, "tiny"), 1);
+        assert_eq!(found(Severity::Warning, ""), 2);
+        assert_eq!(page.empty_cells(), [(0, 1), (1, 1)]);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn oversized_tracks_run_into_the_margin() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 1);
+        page.grid = Grid::with_tracks(vec![300.0, 300.0], vec![100.0], 12.0, Margin::default());
+        page.add_block(empty_block("left", 0, 0));
+        page.add_block(empty_block("right", 1, 0));
+        assert_eq!(page.margin_violations(), ["right"]);
+    }
+
+    #[test]
+    fn missing_image_assets_reports_only_absent_files() {
+        let dir = std::env::temp_dir().join(format!("grids-assets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("cover.png"), b"png").unwrap();
+
+        let image = |id: &str, path: &str| Block {
+            content: BlockContent::Image {
+                path: path.to_string(),
+                alt: String::new(),
+            },
+            ..empty_block(id, 0, 0)
+        };
+        let mut page = Page::new(1, PageSize::Letter, 3, 4);
+        page.add_block(image("cover", "cover.png"));
+        page.add_block(image("hero", "hero.png"));
+        let missing = page.missing_image_assets(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(missing, [("hero".to_string(), "hero.png".to_string())]);
+    }
+
+    #[test]
+    fn nine_point_bleed_grows_the_bleed_box_by_eighteen() {
+        let page = Page::new(1, PageSize::Letter, 3, 4);
+        let boxes = page.boxes(9.0);
+        assert_eq!(boxes.trim, Rect::new(0.0, 0.0, 612.0, 792.0));
+        assert_eq!(boxes.bleed.w - boxes.trim.w, 18.0);
+        assert_eq!(boxes.bleed.h - boxes.trim.h, 18.0);
+        assert_eq!((boxes.bleed.x, boxes.bleed.y), (-9.0, -9.0));
+        assert_eq!(boxes.media.w - boxes.bleed.w, 2.0 * MARK_AREA);
+    }
+
+    #[test]
+    fn reading_order_sorts_by_row_then_column() {
+        let mut page = Page::new(1, PageSize::Letter, 3, 3);
+        for (id, col, row) in [("c", 2, 1), ("a", 1, 0), ("d", 0, 2), ("b", 0, 1), ("b2", 0, 1)] {
+            page.add_block(empty_block(id, col, row));
+        }
+        let ids: Vec<&str> = page.blocks_in_reading_order().iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "b2", "c", "d"]);
+    }
 }
@@ -0,0 +1,59 @@
+use crate::grid::Grid;
+use crate::page::PageSize;
+
+/// Pick a grid for `item_count` items of roughly `aspect` (width / height)
+/// on `page`, choosing the column/row split that wastes the least area once
+/// each item is fitted inside its cell.
+///
+/// Ties go to the split with fewer cells, then fewer columns, so the result
+/// is deterministic.
+pub fn suggest_grid(page: PageSize, item_count: usize, aspect: f64) -> Grid {
+    let (pw, ph) = page.dimensions();
+    let count = item_count.max(1) as u32;
+    let aspect = if aspect > 0.0 { aspect } else { 1.0 };
+
+    let mut best: Option<(f64, u32, Grid)> = None;
+    for columns in 1..=count {
+        let rows = count.div_ceil(columns);
+        let grid = Grid::new(columns, rows, pw, ph);
+        if grid.column_width <= 0.0 || grid.row_height <= 0.0 {
+            continue;
+        }
+
+        let item_w = grid.column_width.min(grid.row_height * aspect);
+        let item_h = item_w / aspect;
+        let usable = (pw - grid.margin.left - grid.margin.right)
+            * (ph - grid.margin.top - grid.margin.bottom);
+        let waste = usable - count as f64 * item_w * item_h;
+        let cells = columns * rows;
+
+        let better = match &best {
+            None => true,
+            Some((best_waste, best_cells, _)) => {
+                waste < *best_waste - f64::EPSILON
+                    || ((waste - *best_waste).abs() <= f64::EPSILON && cells < *best_cells)
+            }
+        };
+        if better {
+            best = Some((waste, cells, grid));
+        }
+    }
+
+    best.map(|(_, _, grid)| grid)
+        .unwrap_or_else(|| Grid::new(1, count, pw, ph))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn six_items_on_letter_get_a_two_by_three_grid() {
+        for aspect in [1.0, 0.75, 1.5] {
+            let grid = suggest_grid(PageSize::Letter, 6, aspect);
+            let split = (grid.columns, grid.rows);
+            assert!(split == (2, 3) || split == (3, 2), "{aspect}: {split:?}");
+            assert_eq!(grid.cells().count(), 6);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use crate::page::DEFAULT_SAFE_INSET;
+
+/// Which checks [`Page::preflight`](crate::page::Page::preflight) runs.
+#[derive(Debug, Clone)]
+pub struct PreflightOptions {
+    /// Safe-zone inset from the trim in points; `None` skips the check.
+    pub safe_inset: Option<f64>,
+    /// Smallest text size, in points, considered legible in print.
+    pub min_font_size: f64,
+    /// Directory image paths are relative to; `None` skips the asset check.
+    pub asset_dir: Option<PathBuf>,
+    /// Warn about grid cells no block covers.
+    pub empty_cells: bool,
+}
+
+impl Default for PreflightOptions {
+    fn default() -> Self {
+        Self {
+            safe_inset: Some(DEFAULT_SAFE_INSET),
+            min_font_size: 6.0,
+            asset_dir: None,
+            empty_cells: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Prints, but probably not as intended.
+    Warning,
+    /// Can't print as laid out.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflightIssue {
+    pub severity: Severity,
+    /// The block at fault; empty for issues about the page as a whole.
+    pub block_id: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for PreflightIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let level = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        if self.block_id.is_empty() {
+            write!(f, "{level}: {}", self.message)
+        } else {
+            write!(f, "{level}: block `{}`: {}", self.block_id, self.message)
+        }
+    }
+}
+
+/// Everything preflight found, in check order.
+#[derive(Debug, Clone, Default)]
+pub struct PreflightReport {
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl PreflightReport {
+    /// True when nothing rises to [`Severity::Error`].
+    pub fn passed(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &PreflightIssue> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &PreflightIssue> {
+        self.with_severity(Severity::Warning)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &PreflightIssue> {
+        self.issues.iter().filter(move |i| i.severity == severity)
+    }
+
+    pub(crate) fn push(&mut self, severity: Severity, block_id: &str, message: String) {
+        self.issues.push(PreflightIssue {
+            severity,
+            block_id: block_id.to_string(),
+            message,
+        });
+    }
+}
@@ -1,5 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+use crate::page::{Block, Page};
 
 /// A node in the decision tree tracking how a design choice was made.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +18,37 @@ pub struct Decision {
     pub influences: Vec<Influence>,
     pub alternatives_considered: Vec<Alternative>,
     pub confidence: f64,
+    /// Free-form labels such as "accessibility" or "client-request".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// External artifacts behind the decision, such as a Figma file or a
+    /// rendered comp.
+    #[serde(default)]
+    pub links: Vec<DecisionLink>,
+}
+
+impl Decision {
+    /// A short, stable hash of what the decision says -- its kind, rationale,
+    /// and influences -- ignoring id, parent, timestamp, and agent. Identical
+    /// content always yields the same id.
+    pub fn content_id(&self) -> String {
+        let content = serde_json::json!([self.kind, self.rationale, self.influences]);
+        format!("{:016x}", fnv1a(content.to_string().as_bytes()))
+    }
+
+    /// The timestamp parsed as RFC 3339, or `None` when it isn't one.
+    pub fn parsed_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// 64-bit FNV-1a; unlike `DefaultHasher` it is stable across builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,9 +60,41 @@ pub enum DecisionKind {
     Content { property: String, value: String },
     Composition { description: String },
     StyleDirection { description: String },
-    Revision { original_decision_id: String, reason: String },
+    /// Supersedes an earlier decision; `replacement` is what it now says.
+    Revision {
+        original_decision_id: String,
+        reason: String,
+        #[serde(default)]
+        replacement: Option<Box<DecisionKind>>,
+    },
 }
 
+impl DecisionKind {
+    /// Section heading for this kind of decision in grouped notes.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DecisionKind::Layout { .. } => "Layout",
+            DecisionKind::Typography { .. } => "Typography",
+            DecisionKind::Color { .. } => "Color",
+            DecisionKind::Content { .. } => "Content",
+            DecisionKind::Composition { .. } => "Composition",
+            DecisionKind::StyleDirection { .. } => "Style Direction",
+            DecisionKind::Revision { .. } => "Revisions",
+        }
+    }
+}
+
+/// Order of the sections in [`DecisionTree::to_design_notes_grouped`].
+const SECTIONS: [&str; 7] = [
+    "Layout",
+    "Typography",
+    "Color",
+    "Content",
+    "Composition",
+    "Style Direction",
+    "Revisions",
+];
+
 /// A reference that influenced a decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Influence {
@@ -45,6 +113,21 @@ pub enum InfluenceSource {
     PriorDecision { decision_id: String },
 }
 
+/// A moodboard reference as cited by one or more decisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoodboardRef {
+    pub board_id: String,
+    pub ref_id: String,
+    pub description: String,
+}
+
+/// A labelled link to an artifact outside the tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionLink {
+    pub label: String,
+    pub url: String,
+}
+
 /// An alternative that was considered but not chosen.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alternative {
@@ -52,6 +135,47 @@ pub struct Alternative {
     pub reason_rejected: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplaceError {
+    NotFound(String),
+    IdMismatch { expected: String, found: String },
+}
+
+impl std::fmt::Display for ReplaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(id) => write!(f, "no decision with id `{id}`"),
+            Self::IdMismatch { expected, found } => {
+                write!(f, "replacement id `{found}` does not match `{expected}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplaceError {}
+
+/// Why [`DecisionTree::try_add`] refused a decision.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProvenanceError {
+    /// The decision's `parent_id` names no decision in the tree.
+    MissingParent { id: String, parent_id: String },
+    /// A decision with this id is already in the tree.
+    DuplicateId(String),
+}
+
+impl std::fmt::Display for ProvenanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingParent { id, parent_id } => {
+                write!(f, "decision `{id}` has unknown parent `{parent_id}`")
+            }
+            Self::DuplicateId(id) => write!(f, "a decision with id `{id}` already exists"),
+        }
+    }
+}
+
+impl std::error::Error for ProvenanceError {}
+
 /// The full decision tree for a project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecisionTree {
@@ -76,22 +200,186 @@ impl DecisionTree {
         self.decisions.push(decision);
     }
 
+    /// Like [`add`](Self::add), but refuses a decision whose id is already
+    /// taken or whose `parent_id` isn't in the tree. `add` stays unchecked
+    /// for bulk loading, where parents may arrive after their children.
+    pub fn try_add(&mut self, decision: Decision) -> Result<(), ProvenanceError> {
+        if self.index.contains_key(&decision.id) {
+            return Err(ProvenanceError::DuplicateId(decision.id));
+        }
+        if let Some(parent_id) = &decision.parent_id {
+            if !self.index.contains_key(parent_id) {
+                return Err(ProvenanceError::MissingParent {
+                    id: decision.id,
+                    parent_id: parent_id.clone(),
+                });
+            }
+        }
+        self.add(decision);
+        Ok(())
+    }
+
+    /// Add `decision` under its [`Decision::content_id`], returning the id.
+    /// Re-adding the same content is a no-op, so re-running an agent is
+    /// idempotent.
+    pub fn add_auto(&mut self, mut decision: Decision) -> String {
+        let id = decision.content_id();
+        if !self.index.contains_key(&id) {
+            decision.id = id.clone();
+            self.add(decision);
+        }
+        id
+    }
+
     pub fn get(&self, id: &str) -> Option<&Decision> {
         self.index.get(id).map(|&i| &self.decisions[i])
     }
 
-    /// Walk ancestors from a decision back to the root(s).
+    /// Swap the decision `id` for `new` in place, returning the old one. The
+    /// replacement must keep the same id so children stay linked.
+    pub fn replace(&mut self, id: &str, new: Decision) -> Result<Decision, ReplaceError> {
+        if new.id != id {
+            return Err(ReplaceError::IdMismatch {
+                expected: id.to_string(),
+                found: new.id,
+            });
+        }
+        let &idx = self
+            .index
+            .get(id)
+            .ok_or_else(|| ReplaceError::NotFound(id.to_string()))?;
+        let old = std::mem::replace(&mut self.decisions[idx], new);
+        self.index.insert(id.to_string(), idx);
+        Ok(old)
+    }
+
+    /// Record a revision of `original_id`: a new decision, parented to the
+    /// original, whose kind carries `reason` and `new_kind`. Returns the new
+    /// decision's id.
+    pub fn revise(
+        &mut self,
+        original_id: &str,
+        reason: &str,
+        new_kind: DecisionKind,
+        agent: &str,
+    ) -> Result<String, ReplaceError> {
+        let original = self
+            .get(original_id)
+            .ok_or_else(|| ReplaceError::NotFound(original_id.to_string()))?;
+        let confidence = original.confidence;
+        let id = (1..)
+            .map(|n| format!("{original_id}-rev{n}"))
+            .find(|id| !self.index.contains_key(id))
+            .unwrap_or_default();
+        self.add(Decision {
+            id: id.clone(),
+            parent_id: Some(original_id.to_string()),
+            timestamp: Utc::now().to_rfc3339(),
+            agent: agent.to_string(),
+            kind: DecisionKind::Revision {
+                original_decision_id: original_id.to_string(),
+                reason: reason.to_string(),
+                replacement: Some(Box::new(new_kind)),
+            },
+            rationale: reason.to_string(),
+            influences: vec![Influence {
+                source: InfluenceSource::PriorDecision {
+                    decision_id: original_id.to_string(),
+                },
+                relevance: "revised".to_string(),
+                weight: 1.0,
+            }],
+            alternatives_considered: Vec::new(),
+            confidence,
+            tags: Vec::new(),
+            links: Vec::new(),
+        });
+        Ok(id)
+    }
+
+    /// The decision currently standing in for `id`: the latest revision of
+    /// it, followed through revisions of revisions, or `id` itself when it
+    /// was never revised. `None` when `id` isn't in the tree.
+    pub fn current_version(&self, id: &str) -> Option<&Decision> {
+        let mut current = self.get(id)?;
+        let mut seen = HashSet::new();
+        while seen.insert(current.id.as_str()) {
+            match self.direct_revision(&current.id) {
+                Some(d) => current = d,
+                None => break,
+            }
+        }
+        Some(current)
+    }
+
+    /// The end of `id`'s revision chain, or `None` when it was never
+    /// revised (or isn't in the tree).
+    pub fn latest_revision_of(&self, id: &str) -> Option<&Decision> {
+        self.current_version(id).filter(|d| d.id != id)
+    }
+
+    /// The most recent revision made directly to `id`.
+    fn direct_revision(&self, id: &str) -> Option<&Decision> {
+        self.decisions.iter().rev().find(|d| revises(d, id))
+    }
+
+    /// Walk ancestors from a decision back to the root(s). If the parents
+    /// loop back on themselves, the chain stops before the first repeat.
     pub fn lineage(&self, id: &str) -> Vec<&Decision> {
+        self.walk_parents(id).0
+    }
+
+    /// Decisions whose parent is `id`, in tree order. Empty for an unknown id.
+    pub fn children(&self, id: &str) -> Vec<&Decision> {
+        if !self.index.contains_key(id) {
+            return Vec::new();
+        }
+        self.decisions
+            .iter()
+            .filter(|d| d.parent_id.as_deref() == Some(id))
+            .collect()
+    }
+
+    /// Everything that followed from `id`: its children, then theirs,
+    /// breadth-first. Each decision appears once, even if parents loop.
+    pub fn descendants(&self, id: &str) -> Vec<&Decision> {
+        let mut found = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::from([id]);
+        let mut queue = std::collections::VecDeque::from([id]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.children(current) {
+                if seen.insert(child.id.as_str()) {
+                    queue.push_back(child.id.as_str());
+                    found.push(child);
+                }
+            }
+        }
+        found
+    }
+
+    /// Whether any decision's chain of parents loops back on itself, as
+    /// hand-edited JSON can make it do.
+    pub fn has_cycle(&self) -> bool {
+        self.decisions.iter().any(|d| self.walk_parents(&d.id).1)
+    }
+
+    /// The ancestor chain from `id`, and whether it was cut short by a
+    /// cycle.
+    fn walk_parents(&self, id: &str) -> (Vec<&Decision>, bool) {
         let mut chain = Vec::new();
+        let mut seen = HashSet::new();
         let mut current = id;
         while let Some(d) = self.get(current) {
+            if !seen.insert(d.id.as_str()) {
+                return (chain, true);
+            }
             chain.push(d);
             match &d.parent_id {
                 Some(pid) => current = pid,
                 None => break,
             }
         }
-        chain
+        (chain, false)
     }
 
     /// All decisions that cite a specific influence source.
@@ -120,54 +408,225 @@ impl DecisionTree {
             .collect()
     }
 
-    /// Generate a markdown design notes document.
+    /// Distinct moodboard references cited anywhere in the tree, in the order
+    /// they are first cited.
+    pub fn moodboard_refs(&self) -> Vec<MoodboardRef> {
+        let mut seen = HashSet::new();
+        let mut refs = Vec::new();
+        for inf in self.decisions.iter().flat_map(|d| &d.influences) {
+            if let InfluenceSource::Moodboard { board_id, ref_id, description } = &inf.source {
+                if seen.insert((board_id.as_str(), ref_id.as_str())) {
+                    refs.push(MoodboardRef {
+                        board_id: board_id.clone(),
+                        ref_id: ref_id.clone(),
+                        description: description.clone(),
+                    });
+                }
+            }
+        }
+        refs
+    }
+
+    /// All decisions carrying a specific tag.
+    pub fn decisions_with_tag(&self, tag: &str) -> Vec<&Decision> {
+        self.decisions
+            .iter()
+            .filter(|d| d.tags.iter().any(|t| t == tag))
+            .collect()
+    }
+
+    /// Everything one agent decided, in tree order.
+    pub fn by_agent(&self, agent: &str) -> Vec<&Decision> {
+        self.decisions.iter().filter(|d| d.agent == agent).collect()
+    }
+
+    /// Decisions whose kind matches `pred`, e.g.
+    /// `tree.by_kind(|k| matches!(k, DecisionKind::Color { .. }))`.
+    pub fn by_kind(&self, pred: impl Fn(&DecisionKind) -> bool) -> Vec<&Decision> {
+        self.decisions.iter().filter(|d| pred(&d.kind)).collect()
+    }
+
+    /// How many decisions each agent made.
+    pub fn count_by_agent(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for d in &self.decisions {
+            *counts.entry(d.agent.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Decisions made at or after `start` and before `end`, oldest first.
+    /// Decisions without a parseable timestamp are left out; see
+    /// [`invalid_timestamps`](Self::invalid_timestamps).
+    pub fn in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&Decision> {
+        self.timed()
+            .into_iter()
+            .filter(|(t, _)| (start..end).contains(t))
+            .map(|(_, d)| d)
+            .collect()
+    }
+
+    /// Every decision with a parseable timestamp, oldest first. Decisions
+    /// made at the same instant keep insertion order.
+    pub fn chronological(&self) -> Vec<&Decision> {
+        self.timed().into_iter().map(|(_, d)| d).collect()
+    }
+
+    /// Decisions whose timestamp isn't RFC 3339, which the chronological
+    /// views skip.
+    pub fn invalid_timestamps(&self) -> Vec<&Decision> {
+        self.decisions.iter().filter(|d| d.parsed_time().is_none()).collect()
+    }
+
+    fn timed(&self) -> Vec<(DateTime<Utc>, &Decision)> {
+        let mut timed: Vec<_> = self
+            .decisions
+            .iter()
+            .filter_map(|d| Some((d.parsed_time()?, d)))
+            .collect();
+        timed.sort_by_key(|(t, _)| *t);
+        timed
+    }
+
+    /// Generate a markdown design notes document. Revisions follow the
+    /// decision they revise, and a revised decision is marked as such, so
+    /// the notes read as the design's final state.
     pub fn to_design_notes(&self) -> String {
         let mut md = format!("# Design Notes: {}\n\n", self.project_id);
-
+        let mut seen = HashSet::new();
         for d in &self.decisions {
-            md.push_str(&format!("## {}\n\n", d.id));
-            md.push_str(&format!("**Agent:** {} | **Confidence:** {:.0}%\n\n", d.agent, d.confidence * 100.0));
-            md.push_str(&format!("**Rationale:** {}\n\n", d.rationale));
-
-            if !d.influences.is_empty() {
-                md.push_str("**Influences:**\n");
-                for inf in &d.influences {
-                    let src = match &inf.source {
-                        InfluenceSource::Book { title, excerpt, .. } => {
-                            format!("Book: *{}* -- \"{}\"", title, truncate(excerpt, 80))
-                        }
-                        InfluenceSource::Moodboard { description, .. } => {
-                            format!("Moodboard: {}", description)
-                        }
-                        InfluenceSource::AgentKnowledge { agent, note, .. } => {
-                            format!("Agent {}: {}", agent, note)
-                        }
-                        InfluenceSource::UserDirection { input } => {
-                            format!("User: {}", input)
-                        }
-                        InfluenceSource::PriorDecision { decision_id } => {
-                            format!("Prior decision: {}", decision_id)
-                        }
-                    };
-                    md.push_str(&format!("- {} (weight: {:.1})\n", src, inf.weight));
-                }
-                md.push('\n');
+            let revises_known = match &d.kind {
+                DecisionKind::Revision {
+                    original_decision_id,
+                    ..
+                } => self.get(original_decision_id).is_some(),
+                _ => false,
+            };
+            if !revises_known {
+                self.push_with_revisions(&mut md, d, &mut seen);
             }
+        }
+        // Revisions that only revise each other have no root to hang from;
+        // write them in tree order rather than drop them.
+        for d in &self.decisions {
+            self.push_with_revisions(&mut md, d, &mut seen);
+        }
+        md
+    }
 
-            if !d.alternatives_considered.is_empty() {
-                md.push_str("**Alternatives considered:**\n");
-                for alt in &d.alternatives_considered {
-                    md.push_str(&format!("- ~~{}~~ -- {}\n", alt.description, alt.reason_rejected));
-                }
-                md.push('\n');
-            }
+    /// Notes for `d`, then for each of its revisions in turn.
+    fn push_with_revisions<'a>(
+        &'a self,
+        md: &mut String,
+        d: &'a Decision,
+        seen: &mut HashSet<&'a str>,
+    ) {
+        if !seen.insert(d.id.as_str()) {
+            return;
+        }
+        push_decision_notes(md, "##", d, None, self.direct_revision(&d.id));
+        for rev in self.decisions.iter().filter(|r| revises(r, &d.id)) {
+            self.push_with_revisions(md, rev, seen);
+        }
+    }
+
+    /// Design notes ordered by score (confidence scaled by total influence
+    /// weight), highest first. Each section notes where the decision sits in
+    /// its lineage. Ties keep insertion order.
+    pub fn to_design_notes_ranked(&self) -> String {
+        let mut ranked: Vec<&Decision> = self.decisions.iter().collect();
+        ranked.sort_by(|a, b| rank_score(b).total_cmp(&rank_score(a)));
 
-            md.push_str("---\n\n");
+        let mut md = format!("# Design Notes (ranked): {}\n\n", self.project_id);
+        for d in ranked {
+            let lineage = self.lineage(&d.id);
+            let path = (lineage.len() > 1).then(|| {
+                lineage.iter().rev().map(|a| a.id.as_str()).collect::<Vec<_>>().join(" > ")
+            });
+            push_decision_notes(&mut md, "##", d, path.as_deref(), self.direct_revision(&d.id));
         }
+        md
+    }
 
+    /// Design notes sectioned by decision kind -- Layout, Typography, Color,
+    /// Content, Composition, Style Direction, then Revisions -- keeping
+    /// insertion order within each section. Empty sections are omitted.
+    pub fn to_design_notes_grouped(&self) -> String {
+        let mut md = format!("# Design Notes: {}\n\n", self.project_id);
+        for section in SECTIONS {
+            let mut decisions = self.decisions.iter().filter(|d| d.kind.label() == section);
+            let Some(first) = decisions.next() else {
+                continue;
+            };
+            md.push_str(&format!("## {}\n\n", section));
+            for d in std::iter::once(first).chain(decisions) {
+                push_decision_notes(&mut md, "###", d, None, self.direct_revision(&d.id));
+            }
+        }
         md
     }
 
+    /// Newline-delimited JSON, one decision per line in insertion order.
+    /// Unlike the array form this can be appended to as decisions are made.
+    pub fn to_ndjson(&self) -> String {
+        let mut out = String::new();
+        for d in &self.decisions {
+            // Decisions are plain data; serializing one cannot fail.
+            out.push_str(&serde_json::to_string(d).unwrap_or_default());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Read back a [`to_ndjson`](Self::to_ndjson) log. Blank lines are
+    /// skipped; the project id is not part of the log so it is passed in.
+    pub fn from_ndjson(project_id: &str, ndjson: &str) -> Result<Self, serde_json::Error> {
+        let mut tree = Self::new(project_id);
+        for line in ndjson.lines().filter(|l| !l.trim().is_empty()) {
+            tree.decisions.push(serde_json::from_str(line)?);
+        }
+        tree.rebuild_index();
+        Ok(tree)
+    }
+
+    /// Read a `decisions.json`, either the bare decision array a scaffolded
+    /// project starts with or a whole serialized tree, and rebuild the
+    /// index. A bare array takes its project id from the enclosing
+    /// directory's name.
+    pub fn load_from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OnDisk {
+            Decisions(Vec<Decision>),
+            Tree(DecisionTree),
+        }
+
+        let path = path.as_ref();
+        let mut tree = match serde_json::from_slice(&std::fs::read(path)?)? {
+            OnDisk::Tree(tree) => tree,
+            OnDisk::Decisions(decisions) => {
+                let project_id = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default();
+                let mut tree = Self::new(&project_id);
+                tree.decisions = decisions;
+                tree
+            }
+        };
+        tree.rebuild_index();
+        Ok(tree)
+    }
+
+    /// Write the decisions as a pretty-printed JSON array, the same shape
+    /// the scaffolder creates, so [`load_from_path`](Self::load_from_path)
+    /// reads it back.
+    pub fn save_to_path(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.decisions)?;
+        std::fs::write(path, json)
+    }
+
     pub fn rebuild_index(&mut self) {
         self.index.clear();
         for (i, d) in self.decisions.iter().enumerate() {
@@ -176,10 +635,447 @@ impl DecisionTree {
     }
 }
 
-fn truncate(s: &str, max: usize) -> &str {
-    if s.len() <= max {
-        s
-    } else {
-        &s[..max]
+/// Design notes covering only the decisions behind one block: its
+/// `decision_ids` and their ancestors, in tree order. `None` when the page
+/// has no block with that id.
+pub fn block_design_notes(page: &Page, block_id: &str, tree: &DecisionTree) -> Option<String> {
+    let block = page.blocks.iter().find(|b| b.id == block_id)?;
+    let relevant: HashSet<&str> = block
+        .decision_ids
+        .iter()
+        .flat_map(|id| tree.lineage(id))
+        .map(|d| d.id.as_str())
+        .collect();
+
+    let mut md = format!("# Design Notes: {} / {}\n\n", tree.project_id, block_id);
+    for d in tree.decisions.iter().filter(|d| relevant.contains(d.id.as_str())) {
+        push_decision_notes(&mut md, "##", d, None, tree.direct_revision(&d.id));
+    }
+    Some(md)
+}
+
+/// Decision ids that blocks on `page` link to but `tree` doesn't have, in
+/// the order blocks first cite them. Empty when every link resolves.
+pub fn validate_block_links(page: &Page, tree: &DecisionTree) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for id in page.blocks.iter().flat_map(|b| &b.decision_ids) {
+        if tree.get(id).is_none() && !missing.contains(id) {
+            missing.push(id.clone());
+        }
+    }
+    missing
+}
+
+/// The blocks on `page` that link to decision `id`.
+pub fn blocks_for_decision<'a>(page: &'a Page, id: &str) -> Vec<&'a Block> {
+    page.blocks
+        .iter()
+        .filter(|b| b.decision_ids.iter().any(|d| d == id))
+        .collect()
+}
+
+/// Whether `d` is a revision of `id`.
+fn revises(d: &Decision, id: &str) -> bool {
+    matches!(
+        &d.kind,
+        DecisionKind::Revision { original_decision_id, .. } if original_decision_id == id
+    )
+}
+
+/// Notes for one decision under a `heading` (`##`, `###`) titled by its id.
+fn push_decision_notes(
+    md: &mut String,
+    heading: &str,
+    d: &Decision,
+    lineage: Option<&str>,
+    revised_by: Option<&Decision>,
+) {
+    match revised_by {
+        Some(_) => md.push_str(&format!("{} ~~{}~~\n\n", heading, d.id)),
+        None => md.push_str(&format!("{} {}\n\n", heading, d.id)),
+    }
+    if let Some(rev) = revised_by {
+        let reason = match &rev.kind {
+            DecisionKind::Revision { reason, .. } => reason.as_str(),
+            _ => rev.rationale.as_str(),
+        };
+        md.push_str(&format!("**Revised by {}:** {}\n\n", rev.id, reason));
+    }
+    if let Some(path) = lineage {
+        md.push_str(&format!("**Lineage:** {}\n\n", path));
+    }
+    md.push_str(&format!("**Agent:** {} | **Confidence:** {:.0}%\n\n", d.agent, d.confidence * 100.0));
+    if !d.tags.is_empty() {
+        md.push_str(&format!("**Tags:** {}\n\n", d.tags.join(", ")));
+    }
+    md.push_str(&format!("**Rationale:** {}\n\n", d.rationale));
+
+    if !d.influences.is_empty() {
+        md.push_str("**Influences:**\n");
+        for inf in &d.influences {
+            let src = match &inf.source {
+                InfluenceSource::Book { title, excerpt, .. } => {
+                    format!("Book: *{}* -- \"{}\"", title, truncate(excerpt, 80))
+                }
+                InfluenceSource::Moodboard { description, .. } => {
+                    format!("Moodboard: {}", description)
+                }
+                InfluenceSource::AgentKnowledge { agent, note, .. } => {
+                    format!("Agent {}: {}", agent, note)
+                }
+                InfluenceSource::UserDirection { input } => {
+                    format!("User: {}", input)
+                }
+                InfluenceSource::PriorDecision { decision_id } => {
+                    format!("Prior decision: {}", decision_id)
+                }
+            };
+            md.push_str(&format!("- {} (weight: {:.1})\n", src, inf.weight));
+        }
+        md.push('\n');
+    }
+
+    if !d.links.is_empty() {
+        md.push_str("**Links:**\n");
+        for link in &d.links {
+            md.push_str(&format!("- [{}]({})\n", link.label, link.url));
+        }
+        md.push('\n');
+    }
+
+    if !d.alternatives_considered.is_empty() {
+        md.push_str("**Alternatives considered:**\n");
+        for alt in &d.alternatives_considered {
+            md.push_str(&format!("- ~~{}~~ -- {}\n", alt.description, alt.reason_rejected));
+        }
+        md.push('\n');
+    }
+
+    md.push_str("---\n\n");
+}
+
+/// Ranking score: confidence, boosted by how strongly the decision is backed.
+fn rank_score(d: &Decision) -> f64 {
+    let weight: f64 = d.influences.iter().map(|inf| inf.weight).sum();
+    d.confidence * (1.0 + weight)
+}
+
+/// `s` cut to at most `max` characters, with an ellipsis when it was cut.
+fn truncate(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(id: &str, parent_id: Option<&str>) -> Decision {
+        Decision {
+            id: id.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            agent: "layout".to_string(),
+            kind: DecisionKind::Composition {
+                description: format!("decision {id}"),
+            },
+            rationale: format!("because {id}"),
+            influences: Vec::new(),
+            alternatives_considered: Vec::new(),
+            confidence: 0.8,
+            tags: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn truncate_cuts_on_a_char_boundary() {
+        let s = format!("{}{}", "a".repeat(79), "é".repeat(10));
+        assert!(!s.is_char_boundary(80));
+        let cut = truncate(&s, 80);
+        assert_eq!(cut, format!("{}é…", "a".repeat(79)));
+        assert_eq!(truncate("short", 80), "short");
+    }
+
+    #[test]
+    fn cyclic_parents_terminate_and_are_flagged() {
+        let mut tree = DecisionTree::new("p");
+        tree.add(decision("a", Some("b")));
+        tree.add(decision("b", Some("a")));
+        assert!(tree.has_cycle());
+        let ids: Vec<&str> = tree.lineage("a").iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+
+        let mut acyclic = DecisionTree::new("p");
+        acyclic.add(decision("root", None));
+        acyclic.add(decision("child", Some("root")));
+        assert!(!acyclic.has_cycle());
+    }
+
+    #[test]
+    fn save_and_load_round_trip_rebuilds_index() {
+        let dir = std::env::temp_dir().join(format!("grids-roundtrip-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("decisions.json");
+
+        let mut tree = DecisionTree::new("ignored");
+        tree.add(decision("root", None));
+        tree.add(decision("child", Some("root")));
+        tree.save_to_path(&path).unwrap();
+        let loaded = DecisionTree::load_from_path(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.project_id, dir.file_name().unwrap().to_string_lossy());
+        assert_eq!(loaded.get("child").map(|d| d.parent_id.as_deref()), Some(Some("root")));
+        assert!(loaded.get("root").is_some());
+    }
+
+    #[test]
+    fn design_notes_keep_mutual_revisions() {
+        let revision_of = |id: &str, original: &str| Decision {
+            kind: DecisionKind::Revision {
+                original_decision_id: original.to_string(),
+                reason: format!("{id} revises {original}"),
+                replacement: None,
+            },
+            ..decision(id, None)
+        };
+        let mut tree = DecisionTree::new("p");
+        tree.add(decision("root", None));
+        tree.add(revision_of("a", "b"));
+        tree.add(revision_of("b", "a"));
+
+        let notes = tree.to_design_notes();
+        for id in ["root", "a", "b"] {
+            assert_eq!(notes.matches(&format!("because {id}")).count(), 1, "{id}");
+        }
+    }
+
+    #[test]
+    fn decisions_filter_by_tag() {
+        let mut tree = DecisionTree::new("p");
+        let tagged = |id: &str, tags: &[&str]| Decision {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..decision(id, None)
+        };
+        tree.add(tagged("contrast", &["accessibility"]));
+        tree.add(tagged("logo", &["client-request"]));
+        tree.add(tagged("type-size", &["accessibility", "client-request"]));
+
+        let ids = |found: Vec<&Decision>| found.iter().map(|d| d.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(tree.decisions_with_tag("accessibility")), ["contrast", "type-size"]);
+        assert_eq!(ids(tree.decisions_with_tag("client-request")), ["logo", "type-size"]);
+        assert!(tree.decisions_with_tag("print").is_empty());
+        assert!(tree.to_design_notes().contains("**Tags:** accessibility, client-request"));
+    }
+
+    #[test]
+    fn decisions_without_tags_still_load() {
+        let json = r#"{
+            "id": "old",
+            "parent_id": null,
+            "timestamp": "2025-06-01T00:00:00Z",
+            "agent": "layout",
+            "kind": {"type": "Composition", "description": "centered"},
+            "rationale": "balance",
+            "influences": [],
+            "alternatives_considered": [],
+            "confidence": 0.5
+        }"#;
+        let d: Decision = serde_json::from_str(json).unwrap();
+        assert!(d.tags.is_empty());
+    }
+
+    #[test]
+    fn ranked_notes_lead_with_confident_well_backed_decisions() {
+        let influence = |weight: f64| Influence {
+            source: InfluenceSource::UserDirection {
+                input: "make it calm".to_string(),
+            },
+            relevance: "tone".to_string(),
+            weight,
+        };
+        let mut tree = DecisionTree::new("p");
+        tree.add(Decision {
+            confidence: 0.3,
+            ..decision("hunch", None)
+        });
+        tree.add(Decision {
+            confidence: 0.9,
+            influences: vec![influence(0.8), influence(0.7)],
+            ..decision("backed", Some("hunch"))
+        });
+
+        let notes = tree.to_design_notes_ranked();
+        let backed = notes.find("## backed").unwrap();
+        let hunch = notes.find("## hunch").unwrap();
+        assert!(backed < hunch);
+        assert!(notes.contains("**Lineage:** hunch > backed"));
+
+        let chronological = tree.to_design_notes();
+        assert!(chronological.find("hunch").unwrap() < chronological.find("backed").unwrap());
+    }
+
+    #[test]
+    fn replace_keeps_position_and_index() {
+        let mut tree = DecisionTree::new("p");
+        tree.add(decision("root", None));
+        tree.add(decision("margins", Some("root")));
+        tree.add(decision("type", Some("margins")));
+
+        let old = tree
+            .replace(
+                "margins",
+                Decision {
+                    rationale: "wider outer margins".to_string(),
+                    ..decision("margins", Some("root"))
+                },
+            )
+            .unwrap();
+        assert_eq!(old.rationale, "because margins");
+        let ids: Vec<&str> = tree.decisions.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, ["root", "margins", "type"]);
+        assert_eq!(tree.get("margins").unwrap().rationale, "wider outer margins");
+        assert_eq!(tree.index["margins"], 1);
+        assert_eq!(tree.lineage("type").len(), 3);
+
+        assert_eq!(
+            tree.replace("margins", decision("other", None)).unwrap_err(),
+            ReplaceError::IdMismatch {
+                expected: "margins".to_string(),
+                found: "other".to_string(),
+            }
+        );
+        assert_eq!(
+            tree.replace("gone", decision("gone", None)).unwrap_err(),
+            ReplaceError::NotFound("gone".to_string())
+        );
+        assert_eq!(tree.decisions.len(), 3);
+    }
+
+    #[test]
+    fn block_notes_cover_its_decisions_and_their_ancestors() {
+        let mut tree = DecisionTree::new("p");
+        tree.add(decision("direction", None));
+        tree.add(decision("palette", Some("direction")));
+        tree.add(decision("headline", None));
+        tree.add(decision("footer", None));
+
+        let mut page = Page::new(1, crate::page::PageSize::Letter, 2, 2);
+        page.add_block(Block {
+            id: "hero".to_string(),
+            col: 0,
+            row: 0,
+            col_span: 1,
+            row_span: 1,
+            content: crate::page::BlockContent::Empty,
+            decision_ids: vec!["palette".to_string(), "headline".to_string()],
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+
+        let notes = block_design_notes(&page, "hero", &tree).unwrap();
+        for id in ["direction", "palette", "headline"] {
+            assert!(notes.contains(&format!("## {id}\n")), "{id}");
+        }
+        assert!(!notes.contains("footer"));
+        assert!(block_design_notes(&page, "missing", &tree).is_none());
+    }
+
+    #[test]
+    fn content_id_depends_only_on_what_the_decision_says() {
+        let a = decision("a", None);
+        let same = Decision {
+            timestamp: "2026-03-01T12:00:00Z".to_string(),
+            agent: "typography".to_string(),
+            kind: a.kind.clone(),
+            rationale: a.rationale.clone(),
+            ..decision("b", Some("a"))
+        };
+        assert_eq!(a.content_id(), same.content_id());
+        assert_eq!(a.content_id().len(), 16);
+        assert_ne!(a.content_id(), decision("c", None).content_id());
+
+        let mut tree = DecisionTree::new("p");
+        let id = tree.add_auto(a.clone());
+        assert_eq!(tree.add_auto(same), id);
+        assert_eq!(tree.decisions.len(), 1);
+    }
+
+    #[test]
+    fn ndjson_round_trip_keeps_order_one_decision_per_line() {
+        let mut tree = DecisionTree::new("p");
+        for (id, parent) in [("root", None), ("grid", Some("root")), ("type", Some("root"))] {
+            tree.add(decision(id, parent));
+        }
+        let ndjson = tree.to_ndjson();
+        assert_eq!(ndjson.lines().count(), 3);
+        for line in ndjson.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value.is_object());
+        }
+
+        let loaded = DecisionTree::from_ndjson("p", &ndjson).unwrap();
+        let ids: Vec<&str> = loaded.decisions.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, ["root", "grid", "type"]);
+        assert_eq!(loaded.children("root").len(), 2);
+        assert!(loaded.get("type").is_some());
+    }
+
+    #[test]
+    fn links_default_empty_and_render_as_markdown() {
+        let json = serde_json::to_value(decision("old", None)).unwrap();
+        let mut json = json.as_object().unwrap().clone();
+        json.remove("links");
+        let old: Decision = serde_json::from_value(json.into()).unwrap();
+        assert!(old.links.is_empty());
+
+        let mut tree = DecisionTree::new("p");
+        tree.add(old);
+        assert!(!tree.to_design_notes().contains("**Links:**"));
+        tree.add(Decision {
+            links: vec![DecisionLink {
+                label: "Figma comp".to_string(),
+                url: "https://figma.com/file/abc".to_string(),
+            }],
+            ..decision("comp", None)
+        });
+        let notes = tree.to_design_notes();
+        assert!(notes.contains("**Links:**\n- [Figma comp](https://figma.com/file/abc)\n"));
+    }
+
+    #[test]
+    fn revise_makes_the_revision_current() {
+        let mut tree = DecisionTree::new("p");
+        tree.add(decision("palette", None));
+        assert_eq!(tree.current_version("palette").unwrap().id, "palette");
+
+        let warmer = DecisionKind::Color {
+            property: "primary".to_string(),
+            value: "terracotta".to_string(),
+        };
+        let id = tree.revise("palette", "client wants warmer", warmer, "color").unwrap();
+        let current = tree.current_version("palette").unwrap();
+        assert_eq!(current.id, id);
+        assert_eq!(current.parent_id.as_deref(), Some("palette"));
+        assert!(matches!(
+            &current.kind,
+            DecisionKind::Revision { original_decision_id, .. } if original_decision_id == "palette"
+        ));
+
+        let softer = || DecisionKind::Color {
+            property: "primary".to_string(),
+            value: "sand".to_string(),
+        };
+        let again = tree.revise("palette", "too warm", softer(), "color").unwrap();
+        assert_ne!(again, id);
+        assert_eq!(tree.current_version("palette").unwrap().id, again);
+        assert_eq!(
+            tree.revise("missing", "why", softer(), "color"),
+            Err(ReplaceError::NotFound("missing".to_string()))
+        );
     }
 }
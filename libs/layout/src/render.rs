@@ -1,91 +1,990 @@
-use crate::page::{Block, BlockContent, Page};
+pub mod cache;
+#[cfg(feature = "png")]
+pub mod png;
+
+pub use cache::{cached_page_to_latex, cached_page_to_svg, RenderCache};
+#[cfg(feature = "png")]
+pub use png::{page_to_png, page_to_png_with};
+
+use crate::color::CmykColor;
+use crate::document::Document;
+use crate::grid::{Grid, Rect};
+use crate::page::{Block, BlockContent, Page, TextAlign, TextStyle, VAlign, MARK_AREA};
+use crate::units::pt_to_mm;
+
+/// The golden ratio, phi.
+const GOLDEN_RATIO: f64 = 1.618_033_988_749_895;
+
+/// A printer's hairline: the thinnest rule most presses hold reliably, in points.
+pub const HAIRLINE: f64 = 0.25;
+
+/// Stroke widths, in points, for the guide and wireframe layers.
+#[derive(Debug, Clone, Copy)]
+pub struct StrokeWeights {
+    /// Grid cell guides.
+    pub guide: f64,
+    /// Outlines around text and image blocks.
+    pub block_outline: f64,
+    /// Dashed outline of empty blocks.
+    pub empty_dash: f64,
+}
+
+impl Default for StrokeWeights {
+    fn default() -> Self {
+        Self {
+            guide: HAIRLINE,
+            block_outline: 0.5,
+            empty_dash: HAIRLINE,
+        }
+    }
+}
+
+/// Average glyph advance as a fraction of the font size, for estimating
+/// whether a line of text fits its block.
+const AVG_GLYPH_WIDTH: f64 = 0.5;
+
+/// Padding between a text block's edge and its text, in points.
+pub(crate) const TEXT_INSET: f64 = 4.0;
+
+/// Estimated advance of `text` set at `font_size`.
+fn text_width(text: &str, font_size: f64) -> f64 {
+    text.chars().count() as f64 * font_size * AVG_GLYPH_WIDTH
+}
+
+/// Breaks `body` into lines no wider than `max_width`, on word boundaries.
+/// Explicit newlines are hard breaks; a single word wider than `max_width`
+/// gets a line of its own rather than being split.
+pub(crate) fn wrap_text(body: &str, max_width: f64, font_size: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in body.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+            let candidate = format!("{line} {word}");
+            if text_width(&candidate, font_size) > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Where a text block's lines are set.
+pub(crate) struct TextPlacement {
+    /// The first baseline: one line below the top inset, snapped to the
+    /// grid's baseline grid.
+    pub(crate) first_baseline: f64,
+    leading: f64,
+    /// How many lines fit above the bottom inset. Always at least one, so
+    /// short blocks still show their first line.
+    pub(crate) fit: usize,
+}
+
+pub(crate) fn place_text(grid: &Grid, block: Rect, style: &TextStyle) -> TextPlacement {
+    let first_baseline = grid.snap_to_baseline(block.y + TEXT_INSET + style.font_size);
+    let leading = style.font_size * style.line_height;
+    let room = block.y + block.h - TEXT_INSET - first_baseline;
+    let fit = if room < 0.0 || leading <= 0.0 {
+        1
+    } else {
+        1 + (room / leading).floor() as usize
+    };
+    TextPlacement {
+        first_baseline,
+        leading,
+        fit,
+    }
+}
+
+impl TextPlacement {
+    /// Move the first baseline so `lines` lines sit where `valign` puts them
+    /// in `block`. Top leaves the baseline-grid placement alone; text that
+    /// already fills the block stays at the top.
+    pub(crate) fn anchor(&mut self, block: Rect, lines: usize, valign: VAlign) {
+        let shown = lines.clamp(1, self.fit) as f64;
+        let last = self.first_baseline + (shown - 1.0) * self.leading;
+        let spare = (block.y + block.h - TEXT_INSET - last).max(0.0);
+        self.first_baseline += match valign {
+            VAlign::Top => 0.0,
+            VAlign::Middle => spare / 2.0,
+            VAlign::Bottom => spare,
+        };
+    }
+}
+
+/// Where a line set with `align` starts in `block` (for the width the
+/// renderer is told), and the SVG `text-anchor` that goes with it.
+fn line_anchor(block: Rect, align: TextAlign) -> (f64, &'static str) {
+    match align {
+        TextAlign::Left | TextAlign::Justify => (block.x + TEXT_INSET, "start"),
+        TextAlign::Center => (block.x + block.w / 2.0, "middle"),
+        TextAlign::Right => (block.x + block.w - TEXT_INSET, "end"),
+    }
+}
+
+/// Something the renderer papered over rather than drew as authored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderWarning {
+    /// An image block's file doesn't exist; a placeholder was drawn.
+    MissingImage { block_id: String, path: String },
+    /// A text block's line is likely wider or taller than the block.
+    TextOverflow { block_id: String },
+    /// A block spans past the last column or row of the grid.
+    BlockOutOfBounds { block_id: String },
+}
+
+impl std::fmt::Display for RenderWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingImage { block_id, path } => {
+                write!(f, "block `{block_id}`: image `{path}` not found")
+            }
+            Self::TextOverflow { block_id } => {
+                write!(f, "block `{block_id}`: text overflows the block")
+            }
+            Self::BlockOutOfBounds { block_id } => {
+                write!(f, "block `{block_id}` falls outside the grid")
+            }
+        }
+    }
+}
+
+/// Optional layers and settings for SVG rendering.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Draw a dashed safe-zone rectangle inset this many points from the trim.
+    pub safe_zone: Option<f64>,
+    pub strokes: StrokeWeights,
+    /// Draw the grid cell guides. On by default; turn off for final artwork.
+    pub show_grid: bool,
+    /// Draw the grid guides over the blocks (semi-transparent) instead of
+    /// under them, for checking alignment.
+    pub guides_on_top: bool,
+    /// Draw faint horizontal guides on the grid's baselines.
+    pub baseline_grid: bool,
+    /// Overlay lines at thirds of the content area.
+    pub rule_of_thirds: bool,
+    /// Overlay lines dividing the content area at the golden ratio.
+    pub golden_ratio: bool,
+    /// Bleed in points: the canvas grows by this much past the trim on
+    /// every side.
+    pub bleed: f64,
+    /// Draw corner crop marks outside the bleed, widening the canvas to
+    /// make room for them.
+    pub crop_marks: bool,
+    /// Inline PNG and JPEG image blocks as base64 data URIs. Images that
+    /// are missing or in another format still get the placeholder box.
+    pub embed_images: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            safe_zone: None,
+            strokes: StrokeWeights::default(),
+            show_grid: true,
+            guides_on_top: false,
+            baseline_grid: false,
+            rule_of_thirds: false,
+            golden_ratio: false,
+            bleed: 0.0,
+            crop_marks: false,
+            embed_images: false,
+        }
+    }
+}
 
 /// Render a page to SVG string.
 pub fn page_to_svg(page: &Page) -> String {
-    let (pw, ph) = page.size.dimensions();
+    page_to_svg_with(page, &RenderOptions::default())
+}
+
+/// Render a page to SVG string with the given options.
+pub fn page_to_svg_with(page: &Page, opts: &RenderOptions) -> String {
+    render_svg(page, opts).0
+}
+
+/// Like [`page_to_svg`], but also report the fallbacks taken while
+/// rendering. Image paths resolve against the working directory.
+pub fn page_to_svg_with_report(page: &Page) -> (String, Vec<RenderWarning>) {
+    render_svg(page, &RenderOptions::default())
+}
+
+/// Space between stacked pages in [`document_to_svg`], in points.
+const PAGE_GAP: f64 = 18.0;
+
+/// Render every page of a document into one SVG, stacked top to bottom in
+/// page-number order on a gray pasteboard.
+pub fn document_to_svg(doc: &Document) -> String {
+    let pages = doc.pages();
+    let width = pages
+        .iter()
+        .map(|p| p.size.dimensions().0)
+        .fold(0.0, f64::max);
+    let height = pages.iter().map(|p| p.size.dimensions().1).sum::<f64>()
+        + PAGE_GAP * pages.len().saturating_sub(1) as f64;
     let mut svg = format!(
-        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {pw} {ph}\" width=\"{pw}\" height=\"{ph}\">"
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" width=\"{width}\" height=\"{height}\">"
     );
+    svg.push_str(&format!(
+        "<rect width=\"{width}\" height=\"{height}\" fill=\"#d0d0d0\"/>"
+    ));
+    let mut y = 0.0;
+    for page in pages {
+        let (pw, ph) = page.size.dimensions();
+        // Nest the page's own <svg> root, centered across the stack.
+        let placed = format!("<svg x=\"{}\" y=\"{y}\" ", (width - pw) / 2.0);
+        svg.push_str(&page_to_svg(page).replacen("<svg ", &placed, 1));
+        y += ph + PAGE_GAP;
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Render each page of a document to its own SVG, in page-number order.
+pub fn document_to_svgs(doc: &Document) -> Vec<String> {
+    doc.pages().iter().map(page_to_svg).collect()
+}
+
+/// A blank grid template: the page background and grid guides, with no
+/// blocks, chrome, or overlays.
+pub fn grid_template_svg(page: &Page) -> String {
+    let mut svg = svg_open(page);
+    render_guides(&mut svg, &page.grid, HAIRLINE, "");
+    svg.push_str("</svg>");
+    svg
+}
 
+/// A cheap preview of `page` for galleries: blocks drawn as solid rects
+/// colored by content type, with no text, guides, or chrome, scaled so the
+/// longer side is `max_px` pixels.
+pub fn page_to_svg_thumbnail(page: &Page, max_px: u32) -> String {
+    let (pw, ph) = page.size.dimensions();
+    let scale = max_px as f64 / pw.max(ph);
+    let (tw, th) = ((pw * scale).round(), (ph * scale).round());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {pw} {ph}\" width=\"{tw}\" height=\"{th}\">"
+    );
     svg.push_str(&format!(
         "<rect width=\"{pw}\" height=\"{ph}\" fill=\"white\"/>"
     ));
+    for block in &page.blocks {
+        let hex;
+        let fill = match &block.content {
+            BlockContent::Text { .. } => "#9aa5b1",
+            BlockContent::Table { .. } => "#b0bec5",
+            BlockContent::Image { .. } => "#d5dde4",
+            BlockContent::Qr { .. } => "#333",
+            BlockContent::Fill { color, color_ref } => {
+                let color = page.resolve_color(color, color_ref.as_deref());
+                hex = color.to_rgb_hex();
+                &hex
+            }
+            BlockContent::Empty => continue,
+        };
+        let Rect { x, y, w, h } =
+            page.grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"{fill}\"/>"
+        ));
+    }
+    svg.push_str("</svg>");
+    svg
+}
 
-    let guide_stroke = "#e0e0e0";
-    for col in 0..page.grid.columns {
-        for row in 0..page.grid.rows {
-            let (x, y) = page.grid.cell_origin(col, row);
-            let (w, h) = page.grid.span_size(1, 1);
+/// The `<svg>` root for `page` and its white background.
+fn svg_open(page: &Page) -> String {
+    let trim = page.boxes(0.0).trim;
+    svg_open_boxed(trim, trim)
+}
+
+/// An `<svg>` root showing `view`, in page coordinates, with white `paper`.
+fn svg_open_boxed(view: Rect, paper: Rect) -> String {
+    let Rect { x, y, w, h } = view;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\" width=\"{w}\" height=\"{h}\">"
+    );
+    let Rect { x, y, w, h } = paper;
+    let origin = if x == 0.0 && y == 0.0 {
+        String::new()
+    } else {
+        format!("x=\"{x}\" y=\"{y}\" ")
+    };
+    svg.push_str(&format!(
+        "<rect {origin}width=\"{w}\" height=\"{h}\" fill=\"white\"/>"
+    ));
+    svg
+}
+
+fn render_svg(page: &Page, opts: &RenderOptions) -> (String, Vec<RenderWarning>) {
+    let (pw, ph) = page.size.dimensions();
+    let boxes = page.boxes(opts.bleed);
+    let view = if opts.crop_marks { boxes.media } else { boxes.bleed };
+    let mut svg = svg_open_boxed(view, boxes.bleed);
+
+    if opts.show_grid && !opts.guides_on_top {
+        render_guides(&mut svg, &page.grid, opts.strokes.guide, "");
+    }
+
+    if opts.baseline_grid {
+        let area = content_area(page);
+        for y in page.grid.baseline_lines() {
             svg.push_str(&format!(
-                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"none\" stroke=\"{guide_stroke}\" stroke-width=\"0.25\"/>"
+                "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#9ccfe8\" stroke-width=\"{HAIRLINE}\"/>",
+                area.x,
+                area.x + area.w
             ));
         }
     }
 
-    for block in &page.blocks {
-        render_block(&mut svg, &page.grid, block);
+    for (index, block) in page.blocks.iter().enumerate() {
+        render_block(&mut svg, page, index, block, opts);
+    }
+
+    if opts.show_grid && opts.guides_on_top {
+        render_guides(&mut svg, &page.grid, opts.strokes.guide, " stroke-opacity=\"0.6\"");
+    }
+
+    if let Some(chrome) = &page.chrome {
+        let m = &page.grid.margin;
+        let cx = pw / 2.0;
+        let placements = [
+            (chrome.header_text(page.number), m.top / 2.0),
+            (chrome.footer_text(page.number), ph - m.bottom / 2.0),
+        ];
+        for (text, cy) in placements {
+            let Some(text) = text else { continue };
+            svg.push_str(&format!(
+                "<text x=\"{cx}\" y=\"{cy}\" text-anchor=\"middle\" dominant-baseline=\"middle\" font-size=\"8\" fill=\"#666\">{}</text>",
+                xml_escape(&text)
+            ));
+        }
+    }
+
+    let content = content_area(page);
+    if opts.rule_of_thirds {
+        render_overlay_lines(&mut svg, content, &[1.0 / 3.0, 2.0 / 3.0], "#2a9df4");
+    }
+    if opts.golden_ratio {
+        let minor = 1.0 - 1.0 / GOLDEN_RATIO;
+        render_overlay_lines(&mut svg, content, &[minor, 1.0 - minor], "#e8a317");
+    }
+
+    if let Some(inset) = opts.safe_zone {
+        let safe_stroke = "#e0457b";
+        let (w, h) = (pw - 2.0 * inset, ph - 2.0 * inset);
+        svg.push_str(&format!(
+            "<rect x=\"{inset}\" y=\"{inset}\" width=\"{w}\" height=\"{h}\" fill=\"none\" stroke=\"{safe_stroke}\" stroke-width=\"0.5\" stroke-dasharray=\"6,3\"/>"
+        ));
+    }
+
+    if let Some(marks) = &page.trim_marks {
+        render_trim_marks(&mut svg, &marks.trims, marks.bleed);
+    }
+
+    if opts.crop_marks {
+        let trim = boxes.trim;
+        let xs = [trim.x, trim.x + trim.w];
+        let ys = [trim.y, trim.y + trim.h];
+        render_crop_marks(&mut svg, trim, &xs, &ys, opts.bleed);
     }
 
     svg.push_str("</svg>");
-    svg
+    (svg, render_warnings(page))
 }
 
-fn render_block(svg: &mut String, grid: &crate::grid::Grid, block: &Block) {
-    let (x, y) = grid.cell_origin(block.col, block.row);
-    let (w, h) = grid.span_size(block.col_span, block.row_span);
+fn render_warnings(page: &Page) -> Vec<RenderWarning> {
+    let grid = &page.grid;
+    let mut warnings = Vec::new();
+    for b in &page.blocks {
+        let block_id = b.id.clone();
+        if !grid.contains(b.col, b.row, b.col_span, b.row_span) {
+            warnings.push(RenderWarning::BlockOutOfBounds {
+                block_id: block_id.clone(),
+            });
+        }
+        match &b.content {
+            BlockContent::Image { path, .. } if !std::path::Path::new(path).is_file() => {
+                warnings.push(RenderWarning::MissingImage {
+                    block_id,
+                    path: path.clone(),
+                });
+            }
+            BlockContent::Text {
+                body,
+                style,
+                style_ref,
+            } => {
+                let style = page.resolve_style(style, style_ref.as_deref());
+                // Mirrors the wrapping in `render_block`: overflow is a word
+                // wider than the block or more lines than fit its height.
+                let rect = grid.cell_rect(b.col, b.row, b.col_span, b.row_span);
+                let max_width = rect.w - 2.0 * TEXT_INSET;
+                let lines = wrap_text(body, max_width, style.font_size);
+                let too_wide = lines
+                    .iter()
+                    .any(|l| text_width(l, style.font_size) > max_width);
+                let placement = place_text(grid, rect, style);
+                let too_tall = placement.first_baseline > rect.y + rect.h;
+                if too_wide || too_tall || lines.len() > placement.fit {
+                    warnings.push(RenderWarning::TextOverflow { block_id });
+                }
+            }
+            _ => {}
+        }
+    }
+    warnings
+}
+
+/// The area inside the page margins.
+fn content_area(page: &Page) -> Rect {
+    let (pw, ph) = page.size.dimensions();
+    let m = page.grid.effective_margin();
+    Rect::new(m.left, m.top, pw - m.left - m.right, ph - m.top - m.bottom)
+}
+
+/// Vertical and horizontal hairlines across `area` at each fraction of its
+/// width and height.
+fn render_overlay_lines(svg: &mut String, area: Rect, fractions: &[f64], stroke: &str) {
+    let (x2, y2) = (area.x + area.w, area.y + area.h);
+    for f in fractions {
+        let x = area.x + area.w * f;
+        svg.push_str(&format!(
+            "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{y2}\" stroke=\"{stroke}\" stroke-width=\"{HAIRLINE}\"/>",
+            area.y
+        ));
+    }
+    for f in fractions {
+        let y = area.y + area.h * f;
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{y}\" x2=\"{x2}\" y2=\"{y}\" stroke=\"{stroke}\" stroke-width=\"{HAIRLINE}\"/>",
+            area.x
+        ));
+    }
+}
+
+/// Gap between the bleed edge and the start of a crop mark, in points.
+const CROP_MARK_GAP: f64 = 3.0;
+
+/// Crop marks for trim lines at `xs` (vertical cuts) and `ys` (horizontal
+/// cuts) around `bounds`: each mark starts `bleed` plus a small gap outside
+/// `bounds` and runs to the edge of the mark area, so marks never print
+/// inside the bleed.
+pub(crate) fn render_crop_marks(
+    svg: &mut String,
+    bounds: Rect,
+    xs: &[f64],
+    ys: &[f64],
+    bleed: f64,
+) {
+    for (x1, y1, x2, y2) in crop_mark_lines(bounds, xs, ys, bleed) {
+        push_mark_line(svg, x1, y1, x2, y2);
+    }
+}
+
+/// Crop marks around a block of `trims` laid out in rows and columns, with a
+/// mark at every distinct cut line.
+pub(crate) fn render_trim_marks(svg: &mut String, trims: &[Rect], bleed: f64) {
+    for (x1, y1, x2, y2) in trim_mark_lines(trims, bleed) {
+        push_mark_line(svg, x1, y1, x2, y2);
+    }
+}
+
+fn push_mark_line(svg: &mut String, x1: f64, y1: f64, x2: f64, y2: f64) {
+    svg.push_str(&format!(
+        "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"#000\" stroke-width=\"{HAIRLINE}\"/>"
+    ));
+}
+
+/// The `(x1, y1, x2, y2)` segments of [`render_crop_marks`].
+fn crop_mark_lines(
+    bounds: Rect,
+    xs: &[f64],
+    ys: &[f64],
+    bleed: f64,
+) -> Vec<(f64, f64, f64, f64)> {
+    let near = bleed + CROP_MARK_GAP;
+    let far = bleed + MARK_AREA;
+    let (top, bottom) = (bounds.y, bounds.y + bounds.h);
+    let (left, right) = (bounds.x, bounds.x + bounds.w);
+    let mut lines = Vec::new();
+    for &x in xs {
+        lines.push((x, top - far, x, top - near));
+        lines.push((x, bottom + near, x, bottom + far));
+    }
+    for &y in ys {
+        lines.push((left - far, y, left - near, y));
+        lines.push((right + near, y, right + far, y));
+    }
+    lines
+}
+
+/// The `(x1, y1, x2, y2)` segments of [`render_trim_marks`].
+fn trim_mark_lines(trims: &[Rect], bleed: f64) -> Vec<(f64, f64, f64, f64)> {
+    if trims.is_empty() {
+        return Vec::new();
+    }
+    let left = trims.iter().map(|r| r.x).fold(f64::INFINITY, f64::min);
+    let top = trims.iter().map(|r| r.y).fold(f64::INFINITY, f64::min);
+    let right = trims.iter().map(|r| r.x + r.w).fold(f64::NEG_INFINITY, f64::max);
+    let bottom = trims.iter().map(|r| r.y + r.h).fold(f64::NEG_INFINITY, f64::max);
+    let bounds = Rect::new(left, top, right - left, bottom - top);
+    let mut xs: Vec<f64> = trims.iter().flat_map(|r| [r.x, r.x + r.w]).collect();
+    let mut ys: Vec<f64> = trims.iter().flat_map(|r| [r.y, r.y + r.h]).collect();
+    for v in [&mut xs, &mut ys] {
+        v.sort_by(f64::total_cmp);
+        v.dedup();
+    }
+    crop_mark_lines(bounds, &xs, &ys, bleed)
+}
+
+fn render_guides(svg: &mut String, grid: &Grid, weight: f64, extra: &str) {
+    let guide_stroke = "#e0e0e0";
+    for (col, row) in grid.cells() {
+        let Rect { x, y, w, h } = grid.cell_rect(col, row, 1, 1);
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"none\" stroke=\"{guide_stroke}\" stroke-width=\"{weight}\"{extra}/>"
+        ));
+    }
+}
+
+/// Draw `block`, the `index`th on `page`.
+fn render_block(
+    svg: &mut String,
+    page: &Page,
+    index: usize,
+    block: &Block,
+    opts: &RenderOptions,
+) {
+    let strokes = &opts.strokes;
+    let grid = &page.grid;
+    let Rect { x, y, w, h } = grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+    let outline = strokes.block_outline;
+    let dash = strokes.empty_dash;
+    let r = block.corner_radius;
+    let round = if r > 0.0 {
+        format!(" rx=\"{r}\" ry=\"{r}\"")
+    } else {
+        String::new()
+    };
+
+    let rotated = block.rotation != 0.0;
+    if rotated {
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        svg.push_str(&format!(
+            "<g transform=\"rotate({} {cx} {cy})\">",
+            block.rotation
+        ));
+    }
+
+    if block.clip {
+        // Block ids are free text and needn't be unique, so the clip is named
+        // by position; the page number keeps it unique in a document SVG.
+        let clip_id = format!("clip-{}-{index}", page.number);
+        svg.push_str(&format!(
+            "<clipPath id=\"{clip_id}\"><rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round}/></clipPath><g clip-path=\"url(#{clip_id})\">"
+        ));
+    }
 
     match &block.content {
-        BlockContent::Text { body, style } => {
+        BlockContent::Text {
+            body,
+            style,
+            style_ref,
+        } => {
+            let style = page.resolve_style(style, style_ref.as_deref());
             let stroke = "#333";
-            let fill = "#111";
+            let fill = text_fill(style);
             svg.push_str(&format!(
-                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"none\" stroke=\"{stroke}\" stroke-width=\"0.5\"/>"
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round} fill=\"none\" stroke=\"{stroke}\" stroke-width=\"{outline}\"/>"
             ));
-            let text_x = x + 4.0;
-            let text_y = y + style.font_size + 4.0;
+            let rect = Rect::new(x, y, w, h);
+            let max_width = w - 2.0 * TEXT_INSET;
+            let (text_x, anchor) = line_anchor(rect, style.align);
+            let lines = wrap_text(body, max_width, style.font_size);
+            let mut placement = place_text(grid, rect, style);
+            placement.anchor(rect, lines.len(), style.valign);
+            let text_y = placement.first_baseline;
             svg.push_str(&format!(
-                "<text x=\"{text_x}\" y=\"{text_y}\" font-family=\"{}\" font-size=\"{}\" fill=\"{fill}\">",
+                "<text x=\"{text_x}\" y=\"{text_y}\" text-anchor=\"{anchor}\" font-family=\"{}\" font-size=\"{}\" fill=\"{fill}\">",
                 style.font_family, style.font_size
             ));
-            svg.push_str(&xml_escape(body));
+            let shown = lines.len().min(placement.fit);
+            for (i, line) in lines.iter().take(shown).enumerate() {
+                let dy = if i == 0 { 0.0 } else { placement.leading };
+                let stretch = if style.align == TextAlign::Justify && i + 1 < shown {
+                    format!(" textLength=\"{max_width}\" lengthAdjust=\"spacing\"")
+                } else {
+                    String::new()
+                };
+                svg.push_str(&format!(
+                    "<tspan x=\"{text_x}\" dy=\"{dy}\"{stretch}>{}</tspan>",
+                    xml_escape(line)
+                ));
+            }
             svg.push_str("</text>");
         }
         BlockContent::Image { path, alt } => {
-            let bg = "#f0f0f0";
-            let stroke = "#999";
-            let text_fill = "#999";
+            let embedded = opts.embed_images.then(|| embeddable_image(path)).flatten();
+            if let Some((mime, bytes)) = embedded {
+                svg.push_str(&format!(
+                    "<image xmlns:xlink=\"http://www.w3.org/1999/xlink\" x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" preserveAspectRatio=\"xMidYMid meet\" xlink:href=\"data:{mime};base64,{}\">",
+                    base64_encode(&bytes)
+                ));
+                if !alt.is_empty() {
+                    svg.push_str(&format!("<title>{}</title>", xml_escape(alt)));
+                }
+                svg.push_str("</image>");
+            } else {
+                let bg = "#f0f0f0";
+                let stroke = "#999";
+                let text_fill = "#999";
+                svg.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round} fill=\"{bg}\" stroke=\"{stroke}\" stroke-width=\"{outline}\"/>"
+                ));
+                let label = if alt.is_empty() { path.as_str() } else { alt.as_str() };
+                let cx = x + w / 2.0;
+                let cy = y + h / 2.0;
+                svg.push_str(&format!(
+                    "<text x=\"{cx}\" y=\"{cy}\" text-anchor=\"middle\" font-size=\"8\" fill=\"{text_fill}\">[{label}]</text>"
+                ));
+            }
+        }
+        BlockContent::Qr { data } => render_qr(svg, data, x, y, w, h),
+        BlockContent::Table {
+            headers,
+            rows,
+            style,
+        } => {
+            let stroke = "#333";
+            let rule = "#ccc";
+            let fill = text_fill(style);
+            svg.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round} fill=\"none\" stroke=\"{stroke}\" stroke-width=\"{outline}\"/>"
+            ));
+            // Equal columns, and equal rows with the header counted as one.
+            let col_w = w / table_columns(headers, rows) as f64;
+            let row_h = h / (rows.len() + 1) as f64;
+            let header_bg = "#f0f0f0";
             svg.push_str(&format!(
-                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"{bg}\" stroke=\"{stroke}\" stroke-width=\"0.5\"/>"
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{row_h}\" fill=\"{header_bg}\"/>"
             ));
-            let label = if alt.is_empty() { path.as_str() } else { alt.as_str() };
-            let cx = x + w / 2.0;
-            let cy = y + h / 2.0;
+            let lines =
+                std::iter::once((headers, "bold")).chain(rows.iter().map(|r| (r, "normal")));
+            for (r, (cells, weight)) in lines.enumerate() {
+                let row_y = y + r as f64 * row_h;
+                if r > 0 {
+                    svg.push_str(&format!(
+                        "<line x1=\"{x}\" y1=\"{row_y}\" x2=\"{}\" y2=\"{row_y}\" stroke=\"{rule}\" stroke-width=\"{HAIRLINE}\"/>",
+                        x + w
+                    ));
+                }
+                for (c, cell) in cells.iter().enumerate() {
+                    let text_x = x + c as f64 * col_w + 4.0;
+                    let text_y = row_y + row_h / 2.0;
+                    svg.push_str(&format!(
+                        "<text x=\"{text_x}\" y=\"{text_y}\" dominant-baseline=\"middle\" font-family=\"{}\" font-size=\"{}\" font-weight=\"{weight}\" fill=\"{fill}\">{}</text>",
+                        style.font_family,
+                        style.font_size,
+                        xml_escape(cell)
+                    ));
+                }
+            }
+        }
+        BlockContent::Fill { color, color_ref } => {
+            let color = page.resolve_color(color, color_ref.as_deref());
             svg.push_str(&format!(
-                "<text x=\"{cx}\" y=\"{cy}\" text-anchor=\"middle\" font-size=\"8\" fill=\"{text_fill}\">[{label}]</text>"
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round} fill=\"{}\"/>",
+                color.to_rgb_hex()
             ));
         }
         BlockContent::Empty => {
             let stroke = "#ccc";
             svg.push_str(&format!(
-                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"none\" stroke=\"{stroke}\" stroke-width=\"0.25\" stroke-dasharray=\"4,2\"/>"
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\"{round} fill=\"none\" stroke=\"{stroke}\" stroke-width=\"{dash}\" stroke-dasharray=\"4,2\"/>"
+            ));
+        }
+    }
+
+    if block.clip {
+        svg.push_str("</g>");
+    }
+    if rotated {
+        svg.push_str("</g>");
+    }
+}
+
+/// Preview fill for text set in `style`.
+fn text_fill(style: &TextStyle) -> String {
+    style
+        .color
+        .as_ref()
+        .map_or_else(|| "#111".to_string(), CmykColor::to_rgb_hex)
+}
+
+/// The MIME type and bytes of the image at `path`, if it reads and its
+/// magic bytes say PNG or JPEG.
+fn embeddable_image(path: &str) -> Option<(&'static str, Vec<u8>)> {
+    let bytes = std::fs::read(path).ok()?;
+    let mime = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else {
+        return None;
+    };
+    Some((mime, bytes))
+}
+
+/// Standard padded base64, as used in data URIs.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Column count of a table: its widest row, header included.
+fn table_columns(headers: &[String], rows: &[Vec<String>]) -> usize {
+    rows.iter()
+        .map(Vec::len)
+        .chain(std::iter::once(headers.len()))
+        .max()
+        .unwrap_or(0)
+        .max(1)
+}
+
+#[cfg(feature = "qr")]
+fn render_qr(svg: &mut String, data: &str, x: f64, y: f64, w: f64, h: f64) {
+    let Ok(code) = qrcode::QrCode::new(data.as_bytes()) else {
+        render_qr_placeholder(svg, data, x, y, w, h);
+        return;
+    };
+    let modules = code.width();
+    let size = w.min(h);
+    let module = size / modules as f64;
+    let ox = x + (w - size) / 2.0;
+    let oy = y + (h - size) / 2.0;
+    svg.push_str(&format!(
+        "<rect x=\"{ox}\" y=\"{oy}\" width=\"{size}\" height=\"{size}\" fill=\"white\"/>"
+    ));
+    for (i, color) in code.to_colors().iter().enumerate() {
+        if *color != qrcode::Color::Dark {
+            continue;
+        }
+        let mx = ox + (i % modules) as f64 * module;
+        let my = oy + (i / modules) as f64 * module;
+        svg.push_str(&format!(
+            "<rect x=\"{mx}\" y=\"{my}\" width=\"{module}\" height=\"{module}\" fill=\"#000\"/>"
+        ));
+    }
+}
+
+#[cfg(not(feature = "qr"))]
+fn render_qr(svg: &mut String, data: &str, x: f64, y: f64, w: f64, h: f64) {
+    render_qr_placeholder(svg, data, x, y, w, h);
+}
+
+fn render_qr_placeholder(svg: &mut String, data: &str, x: f64, y: f64, w: f64, h: f64) {
+    let bg = "#f0f0f0";
+    let stroke = "#999";
+    svg.push_str(&format!(
+        "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"{bg}\" stroke=\"{stroke}\" stroke-width=\"0.5\"/>"
+    ));
+    let cx = x + w / 2.0;
+    let cy = y + h / 2.0;
+    svg.push_str(&format!(
+        "<text x=\"{cx}\" y=\"{cy}\" text-anchor=\"middle\" font-size=\"8\" fill=\"{stroke}\">[QR: {}]</text>",
+        xml_escape(data)
+    ));
+}
+
+/// Escape markup characters and drop control characters that XML 1.0 forbids
+/// (everything below 0x20 except tab, newline, and carriage return), which
+/// can't be written even as character references.
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a page to an HTML fragment: one `<div>` laid out with CSS Grid,
+/// for previewing in a browser with real font rendering. Blocks are emitted
+/// in reading order; image paths are used as-is for `src`.
+pub fn page_to_html(page: &Page) -> String {
+    let (pw, ph) = page.size.dimensions();
+    let grid = &page.grid;
+    let m = grid.effective_margin();
+    let mut html = format!(
+        "<div class=\"grids-page\" style=\"box-sizing:border-box;width:{pw}pt;height:{ph}pt;padding:{}pt {}pt {}pt {}pt;background:white;display:grid;grid-template-columns:{};grid-template-rows:{};gap:{}pt {}pt\">",
+        m.top,
+        m.right,
+        m.bottom,
+        m.left,
+        css_tracks(&grid.column_tracks, grid.columns, grid.column_width),
+        css_tracks(&grid.row_tracks, grid.rows, grid.row_height),
+        grid.gutter_v,
+        grid.gutter_h
+    );
+    for block in page.blocks_in_reading_order() {
+        render_html_block(&mut html, page, block);
+    }
+    html.push_str("</div>");
+    html
+}
+
+/// A `grid-template-*` value: the tracks when set, otherwise `count`
+/// uniform tracks of `size`.
+fn css_tracks(tracks: &[f64], count: u32, size: f64) -> String {
+    if tracks.is_empty() {
+        format!("repeat({count}, {size}pt)")
+    } else {
+        let sizes: Vec<String> = tracks.iter().map(|t| format!("{t}pt")).collect();
+        sizes.join(" ")
+    }
+}
+
+fn render_html_block(html: &mut String, page: &Page, block: &Block) {
+    let mut style = format!(
+        "grid-column:{} / span {};grid-row:{} / span {};box-sizing:border-box;min-width:0;min-height:0",
+        block.col + 1,
+        block.col_span,
+        block.row + 1,
+        block.row_span
+    );
+    if block.corner_radius > 0.0 {
+        style.push_str(&format!(";border-radius:{}pt", block.corner_radius));
+    }
+    if block.clip {
+        style.push_str(";overflow:hidden");
+    }
+    if block.rotation != 0.0 {
+        style.push_str(&format!(";transform:rotate({}deg)", block.rotation));
+    }
+    let id = xml_escape(&block.id);
+
+    match &block.content {
+        BlockContent::Text {
+            body,
+            style: text_style,
+            style_ref,
+        } => {
+            let ts = page.resolve_style(text_style, style_ref.as_deref());
+            let lines: Vec<String> = body.split('\n').map(xml_escape).collect();
+            html.push_str(&format!(
+                "<div data-block=\"{id}\" style=\"{style};padding:{TEXT_INSET}pt;{}\">{}</div>",
+                html_font(ts),
+                lines.join("<br>")
+            ));
+        }
+        BlockContent::Image { path, alt } => {
+            html.push_str(&format!(
+                "<img data-block=\"{id}\" src=\"{}\" alt=\"{}\" style=\"{style};width:100%;height:100%;object-fit:contain\">",
+                xml_escape(path),
+                xml_escape(alt)
+            ));
+        }
+        BlockContent::Qr { data } => {
+            // Reuse the SVG drawing in a unit-square viewport scaled to the block.
+            let mut svg = String::new();
+            render_qr(&mut svg, data, 0.0, 0.0, 100.0, 100.0);
+            html.push_str(&format!(
+                "<svg data-block=\"{id}\" viewBox=\"0 0 100 100\" style=\"{style};width:100%;height:100%\">{svg}</svg>"
+            ));
+        }
+        BlockContent::Table {
+            headers,
+            rows,
+            style: text_style,
+        } => {
+            let row_html = |cells: &[String], tag: &str| {
+                let cells: String = cells
+                    .iter()
+                    .map(|c| format!("<{tag}>{}</{tag}>", xml_escape(c)))
+                    .collect();
+                format!("<tr>{cells}</tr>")
+            };
+            let body: String = rows.iter().map(|r| row_html(r, "td")).collect();
+            html.push_str(&format!(
+                "<table data-block=\"{id}\" style=\"{style};width:100%;table-layout:fixed;border-collapse:collapse;{}\"><thead>{}</thead><tbody>{body}</tbody></table>",
+                html_font(text_style),
+                row_html(headers, "th")
+            ));
+        }
+        BlockContent::Fill { color, color_ref } => {
+            let color = page.resolve_color(color, color_ref.as_deref());
+            html.push_str(&format!(
+                "<div data-block=\"{id}\" style=\"{style};background:{}\"></div>",
+                color.to_rgb_hex()
+            ));
+        }
+        BlockContent::Empty => {
+            html.push_str(&format!(
+                "<div data-block=\"{id}\" style=\"{style};border:{HAIRLINE}pt dashed #ccc\"></div>"
             ));
         }
     }
 }
 
-fn xml_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
+/// Inline CSS font declarations for `style`.
+fn html_font(style: &TextStyle) -> String {
+    format!(
+        "font-family:{};font-size:{}pt;line-height:{};font-weight:{};color:{};text-align:{}",
+        xml_escape(&style.font_family),
+        style.font_size,
+        style.line_height,
+        xml_escape(&style.weight),
+        text_fill(style),
+        style.align.as_str()
+    )
+}
+
+/// The TikZ anchor for a text node in a block `h` points tall, and how far
+/// below the block's top it sits. The node spans the block width, so only
+/// the vertical alignment moves the anchor.
+fn latex_text_anchor(style: &TextStyle, h: f64) -> (&'static str, f64) {
+    match style.valign {
+        VAlign::Top => ("north west", 0.0),
+        VAlign::Middle => ("west", h / 2.0),
+        VAlign::Bottom => ("south west", h),
+    }
 }
 
 /// Render a page to LaTeX string (standalone document).
 pub fn page_to_latex(page: &Page) -> String {
     let (pw, ph) = page.size.dimensions();
-    let pw_cm = pw / 72.0 * 2.54;
-    let ph_cm = ph / 72.0 * 2.54;
-    let m = &page.grid.margin;
+    let pw_cm = pt_to_mm(pw) / 10.0;
+    let ph_cm = pt_to_mm(ph) / 10.0;
+    let m = page.grid.effective_margin();
 
     let mut tex = String::new();
     tex.push_str("\\documentclass{article}\n");
@@ -95,23 +994,54 @@ pub fn page_to_latex(page: &Page) -> String {
         top = m.top, bot = m.bottom, left = m.left, right = m.right
     ));
     tex.push_str("\\usepackage{tikz}\n");
+    if page.blocks.iter().any(|b| matches!(b.content, BlockContent::Qr { .. })) {
+        tex.push_str("\\usepackage{qrcode}\n");
+    }
     tex.push_str("\\pagestyle{empty}\n");
     tex.push_str("\\begin{document}\n");
     tex.push_str("\\noindent\n");
     tex.push_str("\\begin{tikzpicture}[x=1pt,y=-1pt]\n");
 
-    for block in &page.blocks {
-        let (x, y) = page.grid.cell_origin(block.col, block.row);
-        let (w, h) = page.grid.span_size(block.col_span, block.row_span);
+    for (i, block) in page.blocks.iter().enumerate() {
+        let Rect { x, y, w, h } =
+            page.grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+        let color_name = format!("block{i}");
         let bx = x - m.left;
         let by = y - m.top;
 
+        // TikZ angles run counterclockwise on the page; `transform shape`
+        // turns the nodes along with their positions.
+        let rotated = block.rotation != 0.0;
+        if rotated {
+            let (cx, cy) = (bx + w / 2.0, by + h / 2.0);
+            tex.push_str(&format!(
+                "\\begin{{scope}}[rotate around={{{:.1}:({cx:.1},{cy:.1})}},transform shape]\n",
+                -block.rotation
+            ));
+        }
+
+        if block.corner_radius > 0.0 {
+            let r = block.corner_radius;
+            tex.push_str(&format!(
+                "\\draw[rounded corners={r:.1}pt,line width=0.5pt] ({bx:.1},{by:.1}) rectangle ++({w:.1},{h:.1});\n"
+            ));
+        }
+
         match &block.content {
-            BlockContent::Text { body, style } => {
+            BlockContent::Text {
+                body,
+                style,
+                style_ref,
+            } => {
+                let style = page.resolve_style(style, style_ref.as_deref());
                 let fs = style.font_size;
                 let escaped = latex_escape(body);
+                let color = latex_text_color(&mut tex, &color_name, style);
+                let (anchor, dy) = latex_text_anchor(style, h);
+                let align = style.align.as_str();
+                let ny = by + dy;
                 tex.push_str(&format!(
-                    "\\node[anchor=north west,text width={w:.1}pt,font=\\fontsize{{{fs:.1}}}{{\\baselineskip}}\\selectfont] at ({bx:.1},{by:.1}) {{{escaped}}};\n"
+                    "\\node[anchor={anchor},align={align},text width={w:.1}pt{color},font=\\fontsize{{{fs:.1}}}{{\\baselineskip}}\\selectfont] at ({bx:.1},{ny:.1}) {{{escaped}}};\n"
                 ));
             }
             BlockContent::Image { path, .. } => {
@@ -119,8 +1049,85 @@ pub fn page_to_latex(page: &Page) -> String {
                     "\\node[anchor=north west,inner sep=0] at ({bx:.1},{by:.1}) {{\\includegraphics[width={w:.1}pt,height={h:.1}pt]{{{path}}}}};\n"
                 ));
             }
+            BlockContent::Qr { data } => {
+                let size = w.min(h);
+                let escaped = latex_escape(data);
+                tex.push_str(&format!(
+                    "\\node[anchor=north west,inner sep=0] at ({bx:.1},{by:.1}) {{\\qrcode[height={size:.1}pt]{{{escaped}}}}};\n"
+                ));
+            }
+            BlockContent::Table {
+                headers,
+                rows,
+                style,
+            } => {
+                let columns = table_columns(headers, rows);
+                let col_w = w / columns as f64;
+                // p{} widths exclude the default 6pt \tabcolsep on each side.
+                let col_spec = format!("p{{{:.1}pt}}", (col_w - 12.0).max(0.0));
+                let fs = style.font_size;
+                let color = latex_text_color(&mut tex, &color_name, style);
+                let row_tex = |cells: &[String], bold: bool| {
+                    cells
+                        .iter()
+                        .map(|c| {
+                            let c = latex_escape(c);
+                            if bold {
+                                format!("\\textbf{{{c}}}")
+                            } else {
+                                c
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" & ")
+                };
+                let mut body = format!("{} \\\\ \\hline ", row_tex(headers, true));
+                for row in rows {
+                    body.push_str(&format!("{} \\\\ ", row_tex(row, false)));
+                }
+                tex.push_str(&format!(
+                    "\\node[anchor=north west,inner sep=0{color},font=\\fontsize{{{fs:.1}}}{{\\baselineskip}}\\selectfont] at ({bx:.1},{by:.1}) {{\\begin{{tabular}}{{{}}}{body}\\end{{tabular}}}};\n",
+                    col_spec.repeat(columns)
+                ));
+            }
+            BlockContent::Fill { color, color_ref } => {
+                let color = page.resolve_color(color, color_ref.as_deref());
+                latex_define_color(&mut tex, &color_name, color);
+                tex.push_str(&format!(
+                    "\\fill[{color_name}] ({bx:.1},{by:.1}) rectangle ++({w:.1},{h:.1});\n"
+                ));
+            }
             BlockContent::Empty => {}
         }
+
+        if rotated {
+            tex.push_str("\\end{scope}\n");
+        }
+    }
+
+    if let Some(marks) = &page.trim_marks {
+        for (x1, y1, x2, y2) in trim_mark_lines(&marks.trims, marks.bleed) {
+            let (x1, x2) = (x1 - m.left, x2 - m.left);
+            let (y1, y2) = (y1 - m.top, y2 - m.top);
+            tex.push_str(&format!(
+                "\\draw[line width={HAIRLINE}pt] ({x1:.1},{y1:.1}) -- ({x2:.1},{y2:.1});\n"
+            ));
+        }
+    }
+
+    if let Some(chrome) = &page.chrome {
+        let cx = pw / 2.0 - m.left;
+        let placements = [
+            (chrome.header_text(page.number), -m.top / 2.0),
+            (chrome.footer_text(page.number), ph - m.top - m.bottom / 2.0),
+        ];
+        for (text, cy) in placements {
+            let Some(text) = text else { continue };
+            let escaped = latex_escape(&text);
+            tex.push_str(&format!(
+                "\\node[anchor=center,font=\\fontsize{{8}}{{10}}\\selectfont] at ({cx:.1},{cy:.1}) {{{escaped}}};\n"
+            ));
+        }
     }
 
     tex.push_str("\\end{tikzpicture}\n");
@@ -128,6 +1135,30 @@ pub fn page_to_latex(page: &Page) -> String {
     tex
 }
 
+/// Defines `name` as `color` in xcolor's cmyk model, so print output keeps
+/// the process values rather than a screen approximation.
+fn latex_define_color(tex: &mut String, name: &str, color: &CmykColor) {
+    tex.push_str(&format!(
+        "\\definecolor{{{name}}}{{cmyk}}{{{:.2},{:.2},{:.2},{:.2}}}\n",
+        color.c / 100.0,
+        color.m / 100.0,
+        color.y / 100.0,
+        color.k / 100.0
+    ));
+}
+
+/// A `,text=...` node option for `style`'s color, defining the color first;
+/// empty when the style has none.
+fn latex_text_color(tex: &mut String, name: &str, style: &TextStyle) -> String {
+    match &style.color {
+        Some(color) => {
+            latex_define_color(tex, name, color);
+            format!(",text={name}")
+        }
+        None => String::new(),
+    }
+}
+
 fn latex_escape(s: &str) -> String {
     s.replace('\\', "\\textbackslash{}")
         .replace('{', "\\{")
@@ -140,3 +1171,401 @@ fn latex_escape(s: &str) -> String {
         .replace('~', "\\textasciitilde{}")
         .replace('^', "\\textasciicircum{}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::{PageChrome, PageSize, DEFAULT_SAFE_INSET};
+
+    #[test]
+    fn rotated_text_block_is_turned_about_its_center() {
+        let mut page = Page::new(1, PageSize::Letter, 1, 1);
+        page.add_block(Block {
+            id: "spine".to_string(),
+            col: 0,
+            row: 0,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Text {
+                body: "Vertical".to_string(),
+                style: TextStyle::default(),
+                style_ref: None,
+            },
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 90.0,
+        });
+        let Rect { x, y, w, h } = page.grid.cell_rect(0, 0, 1, 1);
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+
+        let svg = page_to_svg(&page);
+        assert!(svg.contains(&format!("<g transform=\"rotate(90 {cx} {cy})\">")));
+
+        let tex = page_to_latex(&page);
+        let (bx, by) = (w / 2.0, h / 2.0);
+        assert!(tex.contains(&format!(
+            "\\begin{{scope}}[rotate around={{-90.0:({bx:.1},{by:.1})}},transform shape]"
+        )));
+        assert!(tex.contains("\\end{scope}"));
+    }
+
+    #[test]
+    fn safe_zone_is_a_dashed_rect_inset_from_the_trim() {
+        let page = Page::new(1, PageSize::Letter, 2, 2);
+        let opts = RenderOptions {
+            safe_zone: Some(DEFAULT_SAFE_INSET),
+            ..RenderOptions::default()
+        };
+        let svg = page_to_svg_with(&page, &opts);
+        assert!(svg.contains(
+            "<rect x=\"9\" y=\"9\" width=\"594\" height=\"774\" fill=\"none\" stroke=\"#e0457b\""
+        ));
+        assert!(!page_to_svg(&page).contains("#e0457b"));
+    }
+
+    #[test]
+    fn clipped_blocks_get_their_own_clip_path() {
+        let mut page = Page::new(2, PageSize::Letter, 2, 1);
+        for col in 0..2 {
+            page.add_block(Block {
+                id: "hero #1".to_string(),
+                col,
+                row: 0,
+                col_span: 1,
+                row_span: 1,
+                content: BlockContent::Image {
+                    path: "hero.png".to_string(),
+                    alt: String::new(),
+                },
+                decision_ids: Vec::new(),
+                clip: true,
+                corner_radius: 0.0,
+                rotation: 0.0,
+            });
+        }
+        let svg = page_to_svg(&page);
+        for id in ["clip-2-0", "clip-2-1"] {
+            assert_eq!(svg.matches(&format!("<clipPath id=\"{id}\">")).count(), 1);
+            assert!(svg.contains(&format!("<g clip-path=\"url(#{id})\">")));
+        }
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn qr_draws_one_rect_per_dark_module() {
+        let code = qrcode::QrCode::new(b"https://example.com").unwrap();
+        assert_eq!(code.width(), 25);
+        let dark = code
+            .to_colors()
+            .iter()
+            .filter(|c| **c == qrcode::Color::Dark)
+            .count();
+
+        let mut svg = String::new();
+        render_qr(&mut svg, "https://example.com", 0.0, 0.0, 100.0, 100.0);
+        assert_eq!(svg.matches("fill=\"#000\"").count(), dark);
+        assert_eq!(svg.matches("width=\"4\" height=\"4\"").count(), dark);
+        assert!(!svg.contains("[QR:"));
+    }
+
+    #[test]
+    fn xml_escape_drops_nul_and_bell() {
+        assert_eq!(xml_escape("A\u{0}B\u{7}C"), "ABC");
+        assert_eq!(xml_escape("tab\there & <now>"), "tab\there &amp; &lt;now&gt;");
+    }
+
+    #[test]
+    fn corner_radius_rounds_only_when_set() {
+        let card = |corner_radius: f64| {
+            let mut page = Page::new(1, PageSize::Letter, 1, 1);
+            page.add_block(Block {
+                id: "card".to_string(),
+                col: 0,
+                row: 0,
+                col_span: 1,
+                row_span: 1,
+                content: BlockContent::Text {
+                    body: "Hello".to_string(),
+                    style: TextStyle::default(),
+                    style_ref: None,
+                },
+                decision_ids: Vec::new(),
+                clip: false,
+                corner_radius,
+                rotation: 0.0,
+            });
+            page_to_svg(&page)
+        };
+        assert!(card(6.0).contains(" rx=\"6\" ry=\"6\""));
+        assert!(!card(0.0).contains("rx="));
+    }
+
+    #[test]
+    fn custom_guide_weight_reaches_the_guides() {
+        let page = Page::new(1, PageSize::Letter, 2, 2);
+        let opts = RenderOptions {
+            strokes: StrokeWeights {
+                guide: 0.1,
+                ..StrokeWeights::default()
+            },
+            ..RenderOptions::default()
+        };
+        let svg = page_to_svg_with(&page, &opts);
+        assert_eq!(svg.matches("stroke=\"#e0e0e0\" stroke-width=\"0.1\"").count(), 4);
+        assert_eq!(StrokeWeights::default().guide, HAIRLINE);
+    }
+
+    #[test]
+    fn footer_page_token_is_centered_in_the_bottom_margin() {
+        let mut page = Page::new(3, PageSize::Letter, 2, 2);
+        page.chrome = Some(PageChrome {
+            header: None,
+            footer: Some("{page}".to_string()),
+        });
+        let (pw, ph) = PageSize::Letter.dimensions();
+        let (cx, cy) = (pw / 2.0, ph - page.grid.margin.bottom / 2.0);
+        let svg = page_to_svg(&page);
+        assert!(svg.contains(&format!(
+            "<text x=\"{cx}\" y=\"{cy}\" text-anchor=\"middle\" dominant-baseline=\"middle\" font-size=\"8\" fill=\"#666\">3</text>"
+        )));
+        assert_eq!(svg.matches("fill=\"#666\">").count(), 1);
+    }
+
+    #[test]
+    fn guides_on_top_follow_the_blocks() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        page.add_block(Block {
+            id: "title".to_string(),
+            col: 0,
+            row: 0,
+            col_span: 2,
+            row_span: 1,
+            content: BlockContent::Text {
+                body: "Title".to_string(),
+                style: TextStyle::default(),
+                style_ref: None,
+            },
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+        let guide = "stroke=\"#e0e0e0\"";
+
+        let below = page_to_svg(&page);
+        assert!(below.find(guide).unwrap() < below.find("<text").unwrap());
+
+        let opts = RenderOptions {
+            guides_on_top: true,
+            ..RenderOptions::default()
+        };
+        let above = page_to_svg_with(&page, &opts);
+        assert!(above.find(guide).unwrap() > above.rfind("</text>").unwrap());
+        assert_eq!(above.matches(guide).count(), 4);
+        assert_eq!(above.matches("stroke-opacity=\"0.6\"").count(), 4);
+    }
+
+    #[test]
+    fn two_by_two_table_places_header_and_body_cells() {
+        let mut page = Page::new(1, PageSize::Letter, 1, 1);
+        page.add_block(Block {
+            id: "sales".to_string(),
+            col: 0,
+            row: 0,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Table {
+                headers: vec!["Region".to_string(), "Units".to_string()],
+                rows: vec![
+                    vec!["North".to_string(), "12".to_string()],
+                    vec!["South".to_string(), "30".to_string()],
+                ],
+                style: TextStyle::default(),
+            },
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+        let Rect { x, y, w, h } = page.grid.cell_rect(0, 0, 1, 1);
+        let (col_w, row_h) = (w / 2.0, h / 3.0);
+        let svg = page_to_svg(&page);
+
+        let cell = |text: &str, col: f64, row: f64, weight: &str| {
+            let (cx, cy) = (x + col * col_w + 4.0, y + row * row_h + row_h / 2.0);
+            format!(
+                "<text x=\"{cx}\" y=\"{cy}\" dominant-baseline=\"middle\" font-family=\"Helvetica\" font-size=\"10\" font-weight=\"{weight}\" fill=\"#111\">{text}</text>"
+            )
+        };
+        for expected in [
+            cell("Region", 0.0, 0.0, "bold"),
+            cell("Units", 1.0, 0.0, "bold"),
+            cell("North", 0.0, 1.0, "normal"),
+            cell("12", 1.0, 1.0, "normal"),
+            cell("South", 0.0, 2.0, "normal"),
+            cell("30", 1.0, 2.0, "normal"),
+        ] {
+            assert!(svg.contains(&expected), "{expected}");
+        }
+    }
+
+    #[test]
+    fn out_of_bounds_block_is_reported() {
+        let mut page = Page::new(1, PageSize::Letter, 3, 3);
+        page.add_block(Block {
+            id: "stray".to_string(),
+            col: 2,
+            row: 0,
+            col_span: 2,
+            row_span: 1,
+            content: BlockContent::Empty,
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+        let (svg, warnings) = page_to_svg_with_report(&page);
+        assert_eq!(
+            warnings,
+            [RenderWarning::BlockOutOfBounds {
+                block_id: "stray".to_string()
+            }]
+        );
+        assert_eq!(svg, page_to_svg(&page));
+    }
+
+    #[test]
+    fn rule_of_thirds_splits_the_content_area() {
+        let page = Page::new(1, PageSize::Letter, 3, 4);
+        let opts = RenderOptions {
+            rule_of_thirds: true,
+            ..RenderOptions::default()
+        };
+        let svg = page_to_svg_with(&page, &opts);
+        let attr = |line: &str, name: &str| -> f64 {
+            let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+            line[start..].split('"').next().unwrap().parse().unwrap()
+        };
+        let lines: Vec<&str> = svg
+            .split("<line")
+            .skip(1)
+            .filter(|l| l.contains("stroke=\"#2a9df4\""))
+            .collect();
+        let (vertical, horizontal): (Vec<&str>, Vec<&str>) =
+            lines.iter().partition(|l| attr(l, "x1") == attr(l, "x2"));
+
+        // Letter with the default 36pt margins: a 540 x 720 content area.
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let xs: Vec<f64> = vertical.iter().map(|l| attr(l, "x1")).collect();
+        let ys: Vec<f64> = horizontal.iter().map(|l| attr(l, "y1")).collect();
+        assert_eq!((xs.len(), ys.len()), (2, 2));
+        assert!(close(xs[0], 36.0 + 180.0) && close(xs[1], 36.0 + 360.0), "{xs:?}");
+        assert!(close(ys[0], 36.0 + 240.0) && close(ys[1], 36.0 + 480.0), "{ys:?}");
+    }
+
+    #[test]
+    fn grid_template_has_guides_but_no_content() {
+        let mut page = Page::new(1, PageSize::Letter, 3, 2);
+        let contents = [
+            BlockContent::Text {
+                body: "Headline".to_string(),
+                style: TextStyle::default(),
+                style_ref: None,
+            },
+            BlockContent::Image {
+                path: "hero.png".to_string(),
+                alt: "Hero".to_string(),
+            },
+        ];
+        for (col, content) in contents.into_iter().enumerate() {
+            page.add_block(Block {
+                id: format!("b{col}"),
+                col: col as u32,
+                row: 0,
+                col_span: 1,
+                row_span: 1,
+                content,
+                decision_ids: Vec::new(),
+                clip: false,
+                corner_radius: 0.0,
+                rotation: 0.0,
+            });
+        }
+        page.chrome = Some(PageChrome {
+            header: Some("Running head".to_string()),
+            footer: None,
+        });
+
+        let svg = grid_template_svg(&page);
+        assert_eq!(svg.matches("stroke=\"#e0e0e0\"").count(), 6);
+        assert!(!svg.contains("<text"));
+        assert!(!svg.contains("<image"));
+        assert!(page_to_svg(&page).contains("<text"));
+    }
+
+    #[test]
+    fn thumbnail_draws_text_as_one_solid_rect() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+        page.add_block(Block {
+            id: "body".to_string(),
+            col: 0,
+            row: 1,
+            col_span: 2,
+            row_span: 1,
+            content: BlockContent::Text {
+                body: "A long paragraph that would wrap over several lines".to_string(),
+                style: TextStyle::default(),
+                style_ref: None,
+            },
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+        let svg = page_to_svg_thumbnail(&page, 120);
+        let Rect { x, y, w, h } = page.grid.cell_rect(0, 1, 2, 1);
+
+        assert!(!svg.contains("<text"));
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"#9aa5b1\"/>"
+        )));
+        assert!(svg.contains("width=\"93\" height=\"120\""));
+    }
+
+    #[test]
+    fn blocks_sharing_a_named_style_follow_it() {
+        let mut page = Page::new(1, PageSize::Letter, 2, 1);
+        for col in 0..2 {
+            page.add_block(Block {
+                id: format!("caption-{col}"),
+                col,
+                row: 0,
+                col_span: 1,
+                row_span: 1,
+                content: BlockContent::Text {
+                    body: "Caption".to_string(),
+                    style: TextStyle::default(),
+                    style_ref: Some("caption".to_string()),
+                },
+                decision_ids: Vec::new(),
+                clip: false,
+                corner_radius: 0.0,
+                rotation: 0.0,
+            });
+        }
+        let sized = |font_size: f64| TextStyle {
+            font_size,
+            ..TextStyle::default()
+        };
+
+        page.styles.insert("caption".to_string(), sized(14.0));
+        assert_eq!(page_to_svg(&page).matches("font-size=\"14\"").count(), 2);
+
+        page.styles.insert("caption".to_string(), sized(18.0));
+        let svg = page_to_svg(&page);
+        assert_eq!(svg.matches("font-size=\"18\"").count(), 2);
+        assert!(!svg.contains("font-size=\"14\""));
+    }
+}
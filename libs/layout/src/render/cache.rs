@@ -0,0 +1,107 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::page::Page;
+
+use super::{page_to_latex, page_to_svg};
+
+/// Memoized render output, one entry per page number, stamped with a hash of
+/// the page's serialized form. An unchanged page is never rendered twice; an
+/// edited page replaces its old entry, so the cache stays the size of the
+/// document however many times it's edited.
+#[derive(Debug, Default)]
+pub struct RenderCache {
+    svg: HashMap<u32, Entry>,
+    latex: HashMap<u32, Entry>,
+    hits: usize,
+    misses: usize,
+}
+
+/// A page's last render. `hash` is `None` when the page couldn't be
+/// serialized, so the entry never matches and the page is always re-rendered.
+#[derive(Debug)]
+struct Entry {
+    hash: Option<u64>,
+    out: String,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn clear(&mut self) {
+        self.svg.clear();
+        self.latex.clear();
+    }
+
+    fn lookup<'a>(
+        &'a mut self,
+        page: &Page,
+        pick: fn(&mut Self) -> &mut HashMap<u32, Entry>,
+        render: fn(&Page) -> String,
+    ) -> &'a str {
+        let hash = page_hash(page);
+        let fresh = hash.is_some()
+            && pick(self)
+                .get(&page.number)
+                .is_some_and(|entry| entry.hash == hash);
+        if fresh {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let out = render(page);
+            pick(self).insert(page.number, Entry { hash, out });
+        }
+        &pick(self)[&page.number].out
+    }
+}
+
+fn page_hash(page: &Page) -> Option<u64> {
+    let json = serde_json::to_string(page).ok()?;
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+pub fn cached_page_to_svg<'a>(cache: &'a mut RenderCache, page: &Page) -> &'a str {
+    cache.lookup(page, |c| &mut c.svg, page_to_svg)
+}
+
+pub fn cached_page_to_latex<'a>(cache: &'a mut RenderCache, page: &Page) -> &'a str {
+    cache.lookup(page, |c| &mut c.latex, page_to_latex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::PageSize;
+
+    #[test]
+    fn hits_unchanged_pages_and_replaces_edited_ones() {
+        let mut cache = RenderCache::new();
+        let mut page = Page::new(1, PageSize::Letter, 2, 2);
+
+        cached_page_to_svg(&mut cache, &page);
+        cached_page_to_svg(&mut cache, &page);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        page.resize(PageSize::A4).unwrap();
+        let svg = cached_page_to_svg(&mut cache, &page).to_string();
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        assert_eq!(svg, page_to_svg(&page));
+        assert_eq!(cache.svg.len(), 1);
+
+        cached_page_to_latex(&mut cache, &page);
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+    }
+}
@@ -0,0 +1,251 @@
+//! Raster previews. Draws the same geometry as the SVG renderer with
+//! tiny-skia; text is greeked as one bar per wrapped line, so no font is
+//! needed.
+
+use tiny_skia::{
+    Color, FillRule, Paint, PathBuilder, Pixmap, Rect as SkRect, Stroke, StrokeDash, Transform,
+};
+
+use crate::color::cmyk_to_srgb;
+use crate::error::LayoutError;
+use crate::grid::Rect;
+use crate::page::{Block, BlockContent, Page, TextAlign, TextStyle};
+
+use super::{
+    place_text, table_columns, text_width, wrap_text, RenderOptions, AVG_GLYPH_WIDTH,
+    TEXT_INSET,
+};
+
+/// Render a page to PNG bytes at `scale` pixels per point (2.0 for a retina
+/// preview).
+pub fn page_to_png(page: &Page, scale: f64) -> Result<Vec<u8>, LayoutError> {
+    page_to_png_with(page, scale, &RenderOptions::default())
+}
+
+/// Like [`page_to_png`], honoring the grid and stroke settings in `opts`.
+/// Bleed, crop marks, overlays and chrome are SVG-only.
+pub fn page_to_png_with(
+    page: &Page,
+    scale: f64,
+    opts: &RenderOptions,
+) -> Result<Vec<u8>, LayoutError> {
+    let (pw, ph) = page.size.dimensions();
+    let (px_w, px_h) = ((pw * scale).ceil() as u32, (ph * scale).ceil() as u32);
+    let mut pixmap = Pixmap::new(px_w, px_h)
+        .ok_or_else(|| LayoutError::Render(format!("cannot rasterize at {px_w}x{px_h}px")))?;
+    pixmap.fill(Color::WHITE);
+    let mut canvas = Canvas {
+        pixmap: &mut pixmap,
+        transform: Transform::from_scale(scale as f32, scale as f32),
+    };
+
+    let grid = &page.grid;
+    if opts.show_grid {
+        for (col, row) in grid.cells() {
+            let cell = grid.cell_rect(col, row, 1, 1);
+            canvas.stroke_rect(cell, 0.0, GUIDE, opts.strokes.guide, None);
+        }
+    }
+
+    for block in &page.blocks {
+        draw_block(&mut canvas, page, block, opts);
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|e| LayoutError::Render(format!("encoding PNG: {e}")))
+}
+
+type Rgb = (u8, u8, u8);
+
+const GUIDE: Rgb = (0xe0, 0xe0, 0xe0);
+const OUTLINE: Rgb = (0x33, 0x33, 0x33);
+const INK: Rgb = (0x11, 0x11, 0x11);
+const PLACEHOLDER: Rgb = (0xf0, 0xf0, 0xf0);
+const PLACEHOLDER_OUTLINE: Rgb = (0x99, 0x99, 0x99);
+const RULE: Rgb = (0xcc, 0xcc, 0xcc);
+
+struct Canvas<'a> {
+    pixmap: &'a mut Pixmap,
+    transform: Transform,
+}
+
+impl Canvas<'_> {
+    fn fill_rect(&mut self, rect: Rect, r: f64, color: Rgb) {
+        let Some(path) = rect_path(rect, r) else {
+            return;
+        };
+        self.pixmap.fill_path(
+            &path,
+            &paint(color),
+            FillRule::Winding,
+            self.transform,
+            None,
+        );
+    }
+
+    fn stroke_rect(
+        &mut self,
+        rect: Rect,
+        r: f64,
+        color: Rgb,
+        width: f64,
+        dash: Option<(f32, f32)>,
+    ) {
+        let Some(path) = rect_path(rect, r) else {
+            return;
+        };
+        let stroke = Stroke {
+            width: width as f32,
+            dash: dash.and_then(|(on, off)| StrokeDash::new(vec![on, off], 0.0)),
+            ..Stroke::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint(color), &stroke, self.transform, None);
+    }
+
+    fn hline(&mut self, x1: f64, x2: f64, y: f64, color: Rgb, width: f64) {
+        let mut pb = PathBuilder::new();
+        pb.move_to(x1 as f32, y as f32);
+        pb.line_to(x2 as f32, y as f32);
+        let Some(path) = pb.finish() else {
+            return;
+        };
+        let stroke = Stroke {
+            width: width as f32,
+            ..Stroke::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint(color), &stroke, self.transform, None);
+    }
+}
+
+fn paint(color: Rgb) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.0, color.1, color.2, 255);
+    paint.anti_alias = true;
+    paint
+}
+
+/// A rectangle path, with corners rounded to `r` when positive.
+fn rect_path(rect: Rect, r: f64) -> Option<tiny_skia::Path> {
+    let Rect { x, y, w, h } = rect;
+    let (x, y, w, h) = (x as f32, y as f32, w as f32, h as f32);
+    let r = (r as f32).min(w / 2.0).min(h / 2.0);
+    if r <= 0.0 {
+        return Some(PathBuilder::from_rect(SkRect::from_xywh(x, y, w, h)?));
+    }
+    let (x2, y2) = (x + w, y + h);
+    let mut pb = PathBuilder::new();
+    pb.move_to(x + r, y);
+    pb.line_to(x2 - r, y);
+    pb.quad_to(x2, y, x2, y + r);
+    pb.line_to(x2, y2 - r);
+    pb.quad_to(x2, y2, x2 - r, y2);
+    pb.line_to(x + r, y2);
+    pb.quad_to(x, y2, x, y2 - r);
+    pb.line_to(x, y + r);
+    pb.quad_to(x, y, x + r, y);
+    pb.close();
+    pb.finish()
+}
+
+fn text_color(style: &TextStyle) -> Rgb {
+    style.color.as_ref().map_or(INK, cmyk_to_srgb)
+}
+
+/// Greeked text: one bar per wrapped line, an x-height tall, sitting on the
+/// line's baseline.
+fn greek_line(canvas: &mut Canvas, x: f64, baseline: f64, width: f64, style: &TextStyle) {
+    let x_height = style.font_size * AVG_GLYPH_WIDTH;
+    canvas.fill_rect(
+        Rect::new(x, baseline - x_height, width, x_height),
+        0.0,
+        text_color(style),
+    );
+}
+
+fn draw_block(canvas: &mut Canvas, page: &Page, block: &Block, opts: &RenderOptions) {
+    let grid = &page.grid;
+    let Rect { x, y, w, h } = grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+    let rect = Rect::new(x, y, w, h);
+    let outline = opts.strokes.block_outline;
+    let r = block.corner_radius;
+    let base = canvas.transform;
+    canvas.transform = base.pre_rotate_at(
+        block.rotation as f32,
+        (x + w / 2.0) as f32,
+        (y + h / 2.0) as f32,
+    );
+
+    match &block.content {
+        BlockContent::Text {
+            body,
+            style,
+            style_ref,
+        } => {
+            let style = page.resolve_style(style, style_ref.as_deref());
+            canvas.stroke_rect(rect, r, OUTLINE, outline, None);
+            let max_width = w - 2.0 * TEXT_INSET;
+            let lines = wrap_text(body, max_width, style.font_size);
+            let mut placement = place_text(grid, rect, style);
+            placement.anchor(rect, lines.len(), style.valign);
+            let shown = lines.len().min(placement.fit);
+            for (i, line) in lines.iter().take(shown).enumerate() {
+                let mut width = text_width(line, style.font_size).min(max_width);
+                let line_x = match style.align {
+                    TextAlign::Left => x + TEXT_INSET,
+                    TextAlign::Center => x + (w - width) / 2.0,
+                    TextAlign::Right => x + w - TEXT_INSET - width,
+                    TextAlign::Justify => {
+                        if i + 1 < shown {
+                            width = max_width;
+                        }
+                        x + TEXT_INSET
+                    }
+                };
+                let baseline = placement.first_baseline + i as f64 * placement.leading;
+                greek_line(canvas, line_x, baseline, width, style);
+            }
+        }
+        BlockContent::Image { .. } => {
+            canvas.fill_rect(rect, r, PLACEHOLDER);
+            canvas.stroke_rect(rect, r, PLACEHOLDER_OUTLINE, outline, None);
+        }
+        BlockContent::Qr { .. } => {
+            let size = w.min(h);
+            canvas.fill_rect(Rect::new(x, y, size, size), 0.0, OUTLINE);
+        }
+        BlockContent::Table {
+            headers,
+            rows,
+            style,
+        } => {
+            canvas.stroke_rect(rect, r, OUTLINE, outline, None);
+            let col_w = w / table_columns(headers, rows) as f64;
+            let row_h = h / (rows.len() + 1) as f64;
+            canvas.fill_rect(Rect::new(x, y, w, row_h), 0.0, PLACEHOLDER);
+            let lines = std::iter::once(headers).chain(rows.iter());
+            for (i, cells) in lines.enumerate() {
+                let row_y = y + i as f64 * row_h;
+                if i > 0 {
+                    canvas.hline(x, x + w, row_y, RULE, super::HAIRLINE);
+                }
+                let baseline = row_y + (row_h + style.font_size * AVG_GLYPH_WIDTH) / 2.0;
+                for (c, cell) in cells.iter().enumerate() {
+                    let width = text_width(cell, style.font_size).min(col_w - TEXT_INSET);
+                    let cell_x = x + c as f64 * col_w + TEXT_INSET;
+                    greek_line(canvas, cell_x, baseline, width, style);
+                }
+            }
+        }
+        BlockContent::Fill { color, color_ref } => {
+            let color = page.resolve_color(color, color_ref.as_deref());
+            canvas.fill_rect(rect, r, cmyk_to_srgb(color));
+        }
+        BlockContent::Empty => {
+            canvas.stroke_rect(rect, r, RULE, opts.strokes.empty_dash, Some((4.0, 2.0)));
+        }
+    }
+    canvas.transform = base;
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::grid::Margin;
+use crate::page::Page;
+use crate::render::page_to_svg;
+
+/// A double-sided item: a front and a back page laid out on the same grid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sheet {
+    pub front: Page,
+    pub back: Page,
+}
+
+impl Sheet {
+    pub fn new(front: Page, back: Page) -> Self {
+        Self { front, back }
+    }
+
+    /// The back page as it prints: margins and block columns are mirrored
+    /// horizontally so they register with the front once the sheet is
+    /// flipped. Only positions move -- block content, including text, is
+    /// left upright and anchored as authored.
+    pub fn mirrored_back(&self) -> Page {
+        let mut back = self.back.clone();
+        let columns = back.grid.columns;
+        // Fixed tracks need not fill the content area, so the new left margin
+        // is whatever the columns leave of the page once the old left margin
+        // moves to the right; on a uniform grid that is the old right margin.
+        let (page_w, _) = back.size.dimensions();
+        let width = back.grid.cell_rect(0, 0, columns, 1).w;
+        let m = &back.grid.margin;
+        back.grid.margin = Margin {
+            left: page_w - m.left - width,
+            right: m.left,
+            ..m.clone()
+        };
+        back.grid.column_tracks.reverse();
+        for block in &mut back.blocks {
+            block.col = columns.saturating_sub(block.col.saturating_add(block.col_span));
+        }
+        back
+    }
+
+    pub fn front_to_svg(&self) -> String {
+        page_to_svg(&self.front)
+    }
+
+    pub fn back_to_svg(&self) -> String {
+        page_to_svg(&self.mirrored_back())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::page::{Block, BlockContent, PageSize, TextStyle};
+    use crate::render::TEXT_INSET;
+
+    fn block(id: &str, col: u32, col_span: u32) -> Block {
+        Block {
+            id: id.to_string(),
+            col,
+            row: 0,
+            col_span,
+            row_span: 1,
+            content: BlockContent::Empty,
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    #[test]
+    fn mirrored_back_reverses_uneven_tracks() {
+        let margin = Margin {
+            left: 20.0,
+            right: 40.0,
+            ..Margin::default()
+        };
+        let mut back = Page::new(2, PageSize::Letter, 3, 1);
+        back.grid = Grid::with_tracks(vec![200.0, 100.0, 100.0], vec![300.0], 10.0, margin);
+        back.add_block(block("wide", 0, 1));
+        back.add_block(block("huge", 1, u32::MAX));
+        let sheet = Sheet::new(Page::new(1, PageSize::Letter, 3, 1), back);
+
+        let mirrored = sheet.mirrored_back();
+        assert_eq!(mirrored.grid.column_tracks, [100.0, 100.0, 200.0]);
+        assert_eq!(mirrored.blocks[0].col, 2);
+        assert_eq!(mirrored.blocks[1].col, 0);
+
+        // The wide column lands where its mirror image falls: the original
+        // left margin in from the right trim.
+        let (pw, _) = PageSize::Letter.dimensions();
+        let rect = mirrored.grid.cell_rect(2, 0, 1, 1);
+        assert_eq!(rect.w, 200.0);
+        assert_eq!(rect.x + rect.w, pw - 20.0);
+    }
+
+    #[test]
+    fn back_text_moves_to_the_mirrored_column_but_stays_readable() {
+        let mut back = Page::new(2, PageSize::Letter, 3, 1);
+        back.add_block(Block {
+            content: BlockContent::Text {
+                body: "Reverse".to_string(),
+                style: TextStyle::default(),
+                style_ref: None,
+            },
+            ..block("name", 0, 1)
+        });
+        let sheet = Sheet::new(Page::new(1, PageSize::Letter, 3, 1), back);
+
+        let mirrored = sheet.mirrored_back();
+        assert_eq!(mirrored.blocks[0].col, 2);
+        let rect = mirrored.grid.cell_rect(2, 0, 1, 1);
+        let svg = sheet.back_to_svg();
+        let text_x = rect.x + TEXT_INSET;
+        assert!(svg.contains(&format!("<text x=\"{text_x}\"")));
+        assert!(svg.contains(">Reverse</tspan>"));
+        assert!(!svg.contains("scale(-"));
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// Points per inch (PostScript points).
+pub const PT_PER_IN: f64 = 72.0;
+/// Millimeters per inch.
+pub const MM_PER_IN: f64 = 25.4;
+
+pub fn pt_to_in(pt: f64) -> f64 {
+    pt / PT_PER_IN
+}
+
+pub fn in_to_pt(inches: f64) -> f64 {
+    inches * PT_PER_IN
+}
+
+pub fn pt_to_mm(pt: f64) -> f64 {
+    pt / PT_PER_IN * MM_PER_IN
+}
+
+pub fn mm_to_pt(mm: f64) -> f64 {
+    mm / MM_PER_IN * PT_PER_IN
+}
+
+pub fn in_to_mm(inches: f64) -> f64 {
+    inches * MM_PER_IN
+}
+
+pub fn mm_to_in(mm: f64) -> f64 {
+    mm / MM_PER_IN
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    Pt,
+    In,
+    Mm,
+}
+
+impl Unit {
+    /// Convert `value` in this unit to points.
+    pub fn to_pt(self, value: f64) -> f64 {
+        match self {
+            Unit::Pt => value,
+            Unit::In => in_to_pt(value),
+            Unit::Mm => mm_to_pt(value),
+        }
+    }
+
+    /// Convert `pt` points to this unit.
+    pub fn from_pt(self, pt: f64) -> f64 {
+        match self {
+            Unit::Pt => pt,
+            Unit::In => pt_to_in(pt),
+            Unit::Mm => pt_to_mm(pt),
+        }
+    }
+
+    /// The unit after this one in the pt -> in -> mm cycle.
+    pub fn next(self) -> Unit {
+        match self {
+            Unit::Pt => Unit::In,
+            Unit::In => Unit::Mm,
+            Unit::Mm => Unit::Pt,
+        }
+    }
+
+    /// `pt` points shown in this unit with its suffix: whole points when
+    /// exact (`72pt`, `36.5pt`), two decimals for inches and millimeters.
+    pub fn format(self, pt: f64) -> String {
+        let value = self.from_pt(pt);
+        match self {
+            Unit::Pt => {
+                let s = format!("{value:.2}");
+                let s = s.trim_end_matches('0').trim_end_matches('.');
+                format!("{s}pt")
+            }
+            Unit::In | Unit::Mm => format!("{value:.2}{}", self.suffix()),
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Unit::Pt => "pt",
+            Unit::In => "in",
+            Unit::Mm => "mm",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_inch_is_72pt_and_25_4mm() {
+        assert_eq!(in_to_pt(1.0), 72.0);
+        assert_eq!(pt_to_in(72.0), 1.0);
+        assert_eq!(in_to_mm(1.0), 25.4);
+        assert_eq!(mm_to_in(25.4), 1.0);
+        assert!((pt_to_mm(72.0) - 25.4).abs() < 1e-12);
+        assert!((mm_to_pt(25.4) - 72.0).abs() < 1e-12);
+        for unit in [Unit::Pt, Unit::In, Unit::Mm] {
+            assert!((unit.from_pt(unit.to_pt(3.0)) - 3.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn a_72pt_width_formats_in_each_unit() {
+        assert_eq!(Unit::Pt.format(72.0), "72pt");
+        assert_eq!(Unit::In.format(72.0), "1.00in");
+        assert_eq!(Unit::Mm.format(72.0), "25.40mm");
+        assert_eq!(Unit::Pt.format(36.5), "36.5pt");
+        assert_eq!(Unit::Pt.next().next().next(), Unit::Pt);
+    }
+}
@@ -0,0 +1,25 @@
+/// Pages a folded zine needs for `content_items` at `per_page` items each,
+/// rounded up to the next multiple of 4 (one folded sheet is four pages).
+/// Always at least one sheet.
+pub fn page_count(content_items: usize, per_page: usize) -> usize {
+    let pages = content_items.div_ceil(per_page.max(1));
+    pages.max(1).next_multiple_of(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_multiples_of_four_need_no_blanks() {
+        assert_eq!(page_count(8, 1), 8);
+        assert_eq!(page_count(24, 2), 12);
+    }
+
+    #[test]
+    fn short_counts_pad_to_a_full_sheet() {
+        assert_eq!(page_count(5, 1), 8);
+        assert_eq!(page_count(7, 2), 4);
+        assert_eq!(page_count(0, 3), 4);
+    }
+}
@@ -0,0 +1,105 @@
+//! Headless renderer: writes a scaffolded project's pages into `output/` in
+//! each format listed under `output.formats`.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use grids_intake::project::{load_pages, ProjectSpec};
+use grids_layout::idml::page_to_idml;
+use grids_layout::page::Page;
+use grids_layout::render::{
+    page_to_html, page_to_latex, page_to_png, page_to_svg_with, RenderOptions,
+};
+use grids_layout::units::in_to_pt;
+
+fn main() -> ExitCode {
+    let Some(dir) = std::env::args().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: grids-render <project-dir>");
+        return ExitCode::FAILURE;
+    };
+
+    match render_project(&dir) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("grids-render: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Renders one page to the bytes of an output file.
+type Renderer<'a> = dyn Fn(&Page) -> Result<Vec<u8>, String> + 'a;
+
+fn render_project(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let yaml = std::fs::read_to_string(dir.join("project.yaml"))
+        .map_err(|e| format!("reading project.yaml: {e}"))?;
+    let (spec, warnings) = ProjectSpec::from_yaml_lenient(&yaml)
+        .map_err(|e| format!("parsing project.yaml: {e}"))?;
+    for warning in warnings {
+        eprintln!("grids-render: project.yaml: {warning}");
+    }
+
+    let problems = spec.validate();
+    if !problems.is_empty() {
+        return Err(format!("invalid spec: {}", problems.join("; ")));
+    }
+
+    let pages = load_pages(dir, &spec)?;
+    for page in &pages {
+        let grid = &page.grid;
+        if grid.column_width <= 0.0 || grid.row_height <= 0.0 {
+            return Err(format!("page {}: grid has no room for its cells", page.number));
+        }
+        for b in &page.blocks {
+            if !grid.contains(b.col, b.row, b.col_span, b.row_span) {
+                return Err(format!("page {}: block `{}` falls outside the grid", page.number, b.id));
+            }
+        }
+    }
+
+    let out_dir = dir.join("output");
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("creating output/: {e}"))?;
+
+    // SVGs go to print, so they carry the spec's bleed and crop marks and
+    // leave out the grid guides.
+    let svg_opts = RenderOptions {
+        show_grid: false,
+        bleed: in_to_pt(spec.physical.bleed_inches),
+        crop_marks: true,
+        ..RenderOptions::default()
+    };
+    let render_svg = |page: &Page| Ok(page_to_svg_with(page, &svg_opts).into_bytes());
+    let render_latex = |page: &Page| Ok(page_to_latex(page).into_bytes());
+    let render_html = |page: &Page| Ok(page_to_html(page).into_bytes());
+    let render_idml = |page: &Page| page_to_idml(page).map_err(|e| e.to_string());
+    // PNGs are screen previews, at 2x for high-density displays.
+    let render_png = |page: &Page| page_to_png(page, 2.0).map_err(|e| e.to_string());
+
+    let mut written = Vec::new();
+    for format in &spec.output.formats {
+        let (ext, render): (&str, &Renderer) = match format.as_str() {
+            "svg" => ("svg", &render_svg),
+            "latex" | "tex" => ("tex", &render_latex),
+            "png" => ("png", &render_png),
+            "html" => ("html", &render_html),
+            "idml" => ("idml", &render_idml),
+            other => {
+                eprintln!("grids-render: skipping unsupported format `{other}`");
+                continue;
+            }
+        };
+        for page in &pages {
+            let path = out_dir.join(format!("page-{}.{ext}", page.number));
+            let bytes = render(page).map_err(|e| format!("page {}: {e}", page.number))?;
+            std::fs::write(&path, bytes)
+                .map_err(|e| format!("writing {}: {e}", path.display()))?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
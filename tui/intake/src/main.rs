@@ -1,63 +1,203 @@
-mod project;
 mod ui;
 mod wizard;
 
 use std::io;
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent,
+        KeyModifiers,
+    },
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
+use grids_intake::project;
 use ratatui::prelude::*;
 
 use wizard::{Step, Wizard};
 
 fn main() -> io::Result<()> {
+    if std::env::args().any(|a| a == "--schema") {
+        let schema = serde_json::to_string_pretty(&Step::schema_all()).map_err(io::Error::other)?;
+        println!("{schema}");
+        return Ok(());
+    }
+
+    if std::env::args().any(|a| a == "--recent") {
+        for p in project::list_projects(std::path::Path::new("projects")) {
+            println!(
+                "{}  {:<32} {:<14} {}",
+                p.modified.format("%Y-%m-%d %H:%M"),
+                p.name,
+                p.project_type,
+                p.dir.display()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut wizard = match (resume_arg()?, edit_arg()?, from_spec_arg()?) {
+        (Some(path), _, _) => Wizard::load_draft(&path)?,
+        (None, Some(path), _) => Wizard::from_yaml(&path)?,
+        (None, None, Some(spec)) => Wizard::from_spec(spec),
+        (None, None, None) => Wizard::new(),
+    };
+    if wizard.input_buf.is_empty() {
+        wizard.load_step_buf();
+    }
+
     enable_raw_mode()?;
     io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout().execute(EnableBracketedPaste)?;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
-    let mut wizard = Wizard::new();
-    wizard.load_step_buf();
 
     loop {
         terminal.draw(|frame| ui::draw(frame, &wizard))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-                break;
+        let key = match dispatch(event::read()?) {
+            Input::Key(key) => key,
+            Input::Paste(text) => {
+                handle_paste(&mut wizard, &text);
+                continue;
+            }
+            // Repaint from scratch so cells from the old size don't linger.
+            Input::Redraw => {
+                terminal.clear()?;
+                continue;
             }
+            Input::Ignore => continue,
+        };
+
+        wizard.notice = None;
+        if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            save_draft(&mut wizard);
+            continue;
+        }
 
-            if key.code == KeyCode::Char('q')
+        let quit = (key.code == KeyCode::Char('c')
+            && key.modifiers.contains(KeyModifiers::CONTROL))
+            || (key.code == KeyCode::Char('q')
                 && wizard.step != Step::Brief
                 && wizard.step != Step::References
                 && wizard.step != Step::Output
                 && wizard.step != Step::Name
                 && wizard.step != Step::Typography
-            {
+                && wizard.step != Step::Color
+                && !wizard.on_new_domain());
+
+        if wizard.confirm_quit {
+            wizard.confirm_quit = false;
+            wizard.field_error = None;
+            if quit || key.code == KeyCode::Char('q') {
                 break;
             }
+            if key.code == KeyCode::Char('s') {
+                wizard.step = Step::Review;
+                wizard.field_index = 0;
+                wizard.load_step_buf();
+                finish(&mut terminal, &mut wizard);
+                continue;
+            }
+        }
 
-            match wizard.step {
-                Step::Name => handle_name_input(&mut wizard, key.code),
-                Step::Physical => handle_physical_input(&mut wizard, key.code),
-                Step::Color => handle_color_input(&mut wizard, key.code),
-                Step::Typography => handle_text_input(&mut wizard, key.code),
-                Step::Brief => handle_multiline_input(&mut wizard, key.code),
-                Step::Domains => handle_domains_input(&mut wizard, key.code),
-                Step::References => handle_multiline_input(&mut wizard, key.code),
-                Step::Output => handle_text_input(&mut wizard, key.code),
-                Step::Review => handle_review_input(&mut wizard, key.code)?,
+        if quit {
+            wizard.commit_current();
+            if wizard.dirty {
+                wizard.confirm_quit = true;
+                wizard.field_error = Some(
+                    "Unsaved changes -- press q again to discard, s to scaffold".to_string(),
+                );
+                continue;
             }
+            break;
+        }
+
+        match wizard.step {
+            Step::Name => handle_name_input(&mut wizard, key.code),
+            Step::Physical => handle_physical_input(&mut wizard, key.code),
+            Step::Grid => handle_grid_input(&mut wizard, key.code),
+            Step::Color => handle_color_input(&mut wizard, key.code),
+            Step::Typography => handle_text_input(&mut wizard, key.code),
+            Step::Brief => handle_multiline_input(&mut wizard, key.code),
+            Step::Domains => handle_domains_input(&mut wizard, key.code),
+            Step::References => handle_references_input(&mut wizard, key),
+            Step::Output => handle_output_input(&mut wizard, key.code),
+            Step::Review => handle_review_input(&mut terminal, &mut wizard, key.code)?,
         }
     }
 
+    io::stdout().execute(DisableBracketedPaste)?;
     disable_raw_mode()?;
     io::stdout().execute(LeaveAlternateScreen)?;
     Ok(())
 }
 
+/// A terminal event as the main loop acts on it.
+#[derive(Debug, PartialEq)]
+enum Input {
+    Key(KeyEvent),
+    Paste(String),
+    /// The terminal changed size; everything must be redrawn.
+    Redraw,
+    Ignore,
+}
+
+fn dispatch(event: Event) -> Input {
+    match event {
+        Event::Key(key) => Input::Key(key),
+        Event::Paste(text) => Input::Paste(text),
+        Event::Resize(_, _) => Input::Redraw,
+        _ => Input::Ignore,
+    }
+}
+
+/// The spec named by `--from <project-dir>`, to start a new project from an
+/// existing one's answers.
+fn from_spec_arg() -> io::Result<Option<project::ProjectSpec>> {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(i) = args.iter().position(|a| a == "--from") else {
+        return Ok(None);
+    };
+    let dir = args
+        .get(i + 1)
+        .ok_or_else(|| io::Error::other("--from needs a project directory"))?;
+    let yaml = std::fs::read_to_string(std::path::Path::new(dir).join("project.yaml"))?;
+    let (spec, _) = project::ProjectSpec::from_yaml_lenient(&yaml).map_err(io::Error::other)?;
+    Ok(Some(spec))
+}
+
+/// The draft named by `--resume <draft>`: a path, or the name of a draft in
+/// `~/.grids/drafts/`.
+fn resume_arg() -> io::Result<Option<std::path::PathBuf>> {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(i) = args.iter().position(|a| a == "--resume") else {
+        return Ok(None);
+    };
+    let draft = args
+        .get(i + 1)
+        .ok_or_else(|| io::Error::other("--resume needs a draft name or path"))?;
+    let path = std::path::PathBuf::from(draft);
+    if path.exists() {
+        return Ok(Some(path));
+    }
+    let dir = project::drafts_dir().ok_or_else(|| io::Error::other("no home directory"))?;
+    Ok(Some(dir.join(format!("{draft}.json"))))
+}
+
+/// The `project.yaml` named by `--edit <path>`, to revise a scaffolded
+/// project's spec in place.
+fn edit_arg() -> io::Result<Option<std::path::PathBuf>> {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(i) = args.iter().position(|a| a == "--edit") else {
+        return Ok(None);
+    };
+    let path = args
+        .get(i + 1)
+        .ok_or_else(|| io::Error::other("--edit needs a project.yaml path"))?;
+    Ok(Some(path.into()))
+}
+
 fn handle_name_input(wizard: &mut Wizard, code: KeyCode) {
     match code {
         KeyCode::Tab => wizard.next_field(),
@@ -88,7 +228,16 @@ fn handle_physical_input(wizard: &mut Wizard, code: KeyCode) {
         KeyCode::Tab => wizard.next_field(),
         KeyCode::BackTab => wizard.prev_field(),
         KeyCode::Enter => wizard.advance(),
-        KeyCode::Backspace => wizard.go_back(),
+        KeyCode::Esc => wizard.go_back(),
+        KeyCode::Backspace if wizard.field_index != 2 => {
+            wizard.input_buf.pop();
+        }
+        KeyCode::Char(c)
+            if wizard.field_index != 2
+                && (c.is_ascii_digit() || c == '.' || c == ' ' || c == 'x') =>
+        {
+            wizard.input_buf.push(c);
+        }
         KeyCode::Left if wizard.field_index == 2 => {
             wizard.sides_index = 0;
         }
@@ -99,12 +248,48 @@ fn handle_physical_input(wizard: &mut Wizard, code: KeyCode) {
     }
 }
 
+fn handle_grid_input(wizard: &mut Wizard, code: KeyCode) {
+    match code {
+        KeyCode::Tab => wizard.next_field(),
+        KeyCode::BackTab => wizard.prev_field(),
+        KeyCode::Enter => wizard.advance(),
+        KeyCode::Esc => wizard.go_back(),
+        KeyCode::Backspace => {
+            wizard.input_buf.pop();
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+            wizard.input_buf.push(c);
+        }
+        _ => {}
+    }
+}
+
 fn handle_color_input(wizard: &mut Wizard, code: KeyCode) {
     match code {
         KeyCode::Tab => wizard.next_field(),
         KeyCode::BackTab => wizard.prev_field(),
+        KeyCode::Enter if wizard.field_index == 3 && !wizard.input_buf.trim().is_empty() => {
+            wizard.add_spot_color()
+        }
         KeyCode::Enter => wizard.advance(),
-        KeyCode::Backspace => wizard.go_back(),
+        KeyCode::Esc => wizard.go_back(),
+        KeyCode::Delete if wizard.field_index == 2 => wizard.toggle_secondary(),
+        KeyCode::Delete if wizard.field_index == 3 => wizard.remove_spot_color(),
+        KeyCode::Up if wizard.field_index == 3 => {
+            wizard.spot_index = wizard.spot_index.saturating_sub(1);
+        }
+        KeyCode::Down
+            if wizard.field_index == 3
+                && wizard.spot_index + 1 < wizard.spec.color.spot_colors.len() =>
+        {
+            wizard.spot_index += 1
+        }
+        KeyCode::Backspace if wizard.field_index > 0 => {
+            wizard.input_buf.pop();
+        }
+        KeyCode::Char(c) if wizard.field_index > 0 => {
+            wizard.input_buf.push(c);
+        }
         KeyCode::Left if wizard.field_index == 0 => {
             if wizard.color_mode_index > 0 {
                 wizard.color_mode_index -= 1;
@@ -135,49 +320,173 @@ fn handle_text_input(wizard: &mut Wizard, code: KeyCode) {
     }
 }
 
-fn handle_multiline_input(wizard: &mut Wizard, code: KeyCode) {
+fn handle_output_input(wizard: &mut Wizard, code: KeyCode) {
     match code {
-        KeyCode::Esc => wizard.advance(),
-        KeyCode::Backspace => {
-            wizard.input_buf.pop();
+        KeyCode::Up if wizard.field_index == 0 => {
+            wizard.format_index = wizard.format_index.saturating_sub(1);
         }
-        KeyCode::Enter => {
-            wizard.input_buf.push('\n');
+        KeyCode::Down if wizard.field_index == 0 => {
+            if wizard.format_index + 1 < wizard.output_formats().len() {
+                wizard.format_index += 1;
+            }
         }
-        KeyCode::Char(c) => {
-            wizard.input_buf.push(c);
+        KeyCode::Char(' ') if wizard.field_index == 0 => wizard.toggle_format(),
+        KeyCode::Char(' ') if wizard.field_index == 1 => {
+            wizard.spec.output.impose = !wizard.spec.output.impose;
+            wizard.dirty = true;
         }
+        KeyCode::Backspace | KeyCode::Char(_) if wizard.field_index < 2 => {}
+        code => handle_text_input(wizard, code),
+    }
+}
+
+fn handle_multiline_input(wizard: &mut Wizard, code: KeyCode) {
+    match code {
+        KeyCode::Tab => wizard.advance(),
+        KeyCode::Esc => wizard.go_back(),
+        KeyCode::Backspace => wizard.delete_back(),
+        KeyCode::Delete => wizard.delete_forward(),
+        KeyCode::Left => wizard.cursor_left(),
+        KeyCode::Right => wizard.cursor_right(),
+        KeyCode::Home => wizard.cursor_home(),
+        KeyCode::End => wizard.cursor_end(),
+        KeyCode::Enter => wizard.insert_char('\n'),
+        KeyCode::Char(c) => wizard.insert_char(c),
         _ => {}
     }
 }
 
+/// Insert a bracketed paste into the free-text fields that take whole
+/// paragraphs, normalizing line endings. Elsewhere a paste is ignored.
+fn handle_paste(wizard: &mut Wizard, text: &str) {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    match wizard.step {
+        Step::Brief | Step::References => wizard.insert_str(&text),
+        Step::Output if wizard.field_index == 2 => wizard.input_buf.push_str(&text),
+        _ => {}
+    }
+}
+
+fn handle_references_input(wizard: &mut Wizard, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            wizard.scan_reference_dir(false)
+        }
+        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::ALT) => {
+            wizard.scan_reference_dir(true)
+        }
+        code => handle_multiline_input(wizard, code),
+    }
+}
+
 fn handle_domains_input(wizard: &mut Wizard, code: KeyCode) {
     match code {
         KeyCode::Tab | KeyCode::Down => wizard.next_field(),
         KeyCode::BackTab | KeyCode::Up => wizard.prev_field(),
+        KeyCode::Enter if wizard.on_new_domain() && !wizard.input_buf.trim().is_empty() => {
+            wizard.add_domain()
+        }
+        KeyCode::Backspace if wizard.on_new_domain() => {
+            wizard.input_buf.pop();
+        }
+        KeyCode::Char(c) if wizard.on_new_domain() => {
+            wizard.input_buf.push(c);
+        }
         KeyCode::Char(' ') => wizard.toggle_domain(),
         KeyCode::Enter => wizard.advance(),
-        KeyCode::Backspace => wizard.go_back(),
+        KeyCode::Esc => wizard.go_back(),
         _ => {}
     }
 }
 
-fn handle_review_input(wizard: &mut Wizard, code: KeyCode) -> io::Result<()> {
+fn handle_review_input(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    wizard: &mut Wizard,
+    code: KeyCode,
+) -> io::Result<()> {
     match code {
-        KeyCode::Backspace => wizard.go_back(),
-        KeyCode::Enter if !wizard.scaffolded => {
-            wizard.commit_current();
-            match wizard.scaffold() {
-                Ok(dir) => {
-                    wizard.scaffolded = true;
-                    eprintln!("Scaffolded project at: {dir}/");
-                }
-                Err(e) => {
-                    eprintln!("Error scaffolding: {e}");
-                }
-            }
-        }
+        KeyCode::Esc => wizard.go_back(),
+        KeyCode::Enter if !wizard.scaffolded => finish(terminal, wizard),
+        KeyCode::Char('o' | 'O') if wizard.confirm_overwrite => scaffold(terminal, wizard, true),
         _ => {}
     }
     Ok(())
 }
+
+/// Save the wizard as a draft to pick up later with `--resume`.
+fn save_draft(wizard: &mut Wizard) {
+    let Some(path) = wizard.draft_path() else {
+        wizard.field_error = Some("Can't save a draft: no home directory".to_string());
+        return;
+    };
+    match wizard.save_draft(&path) {
+        Ok(()) => wizard.notice = Some(format!("Saved draft to {}", path.display())),
+        Err(e) => wizard.field_error = Some(format!("Can't save draft: {e}")),
+    }
+}
+
+/// Save the spec: in place when editing an existing project, otherwise by
+/// scaffolding a new one.
+fn finish(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, wizard: &mut Wizard) {
+    if wizard.editing.is_none() {
+        scaffold(terminal, wizard, false);
+        return;
+    }
+    wizard.commit_current();
+    match wizard.save_edits() {
+        Ok(dir) => {
+            wizard.scaffolded = true;
+            wizard.dirty = false;
+            wizard.field_error = None;
+            wizard.notice = Some(format!("Saved project at: {dir}/"));
+        }
+        Err(e) => {
+            wizard.field_error = Some(format!("Error saving: {e}"));
+        }
+    }
+}
+
+/// Scaffold the project, drawing each step as it happens. An existing
+/// directory is left alone until the user confirms with `overwrite`.
+fn scaffold(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    wizard: &mut Wizard,
+    overwrite: bool,
+) {
+    wizard.commit_current();
+    wizard.confirm_overwrite = false;
+    let current: &Wizard = wizard;
+    let result = current.scaffold_with_progress(overwrite, |event| {
+        let line = event.to_string();
+        let _ = terminal.draw(|frame| ui::draw_with_status(frame, current, Some(&line)));
+    });
+    match result {
+        Ok(dir) => {
+            wizard.scaffolded = true;
+            wizard.dirty = false;
+            wizard.field_error = None;
+            // Best effort: failing to remember the selection isn't worth reporting.
+            let _ = project::save_default_domains(&wizard.spec.domains);
+            eprintln!("Scaffolded project at: {dir}/");
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            wizard.confirm_overwrite = true;
+            wizard.field_error = Some(format!("{e} -- press O to overwrite"));
+        }
+        Err(e) => {
+            wizard.field_error = Some(format!("Error scaffolding: {e}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_alone_asks_for_a_redraw() {
+        assert_eq!(dispatch(Event::Resize(80, 24)), Input::Redraw);
+        assert_eq!(dispatch(Event::Paste("Acme".into())), Input::Paste("Acme".into()));
+        assert_eq!(dispatch(Event::FocusLost), Input::Ignore);
+    }
+}
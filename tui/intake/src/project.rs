@@ -1,10 +1,20 @@
+use std::path::{Path, PathBuf};
+
+pub use grids_layout::color::CmykColor;
+use grids_layout::color::ColorPalette;
+use grids_layout::grid::{Grid, Margin};
+use grids_layout::page::{Block, BlockContent, Page, PageSize, TrimMarks};
+use grids_layout::impose::Imposition;
+use grids_layout::units::{in_to_pt, pt_to_in};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ProjectSpec {
     pub name: String,
     pub project_type: ProjectType,
     pub physical: PhysicalSpec,
+    pub grid: GridSpec,
     pub color: ColorSpec,
     pub typography: TypographySpec,
     pub brief: String,
@@ -13,7 +23,7 @@ pub struct ProjectSpec {
     pub output: OutputSpec,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ProjectType {
     CallingCards,
@@ -47,7 +57,8 @@ impl ProjectType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicalSpec {
     pub item_width_inches: f64,
     pub item_height_inches: f64,
@@ -56,9 +67,255 @@ pub struct PhysicalSpec {
     pub sides: Sides,
     pub bleed_inches: f64,
     pub quantity: u32,
+    #[serde(default)]
+    pub margins: MarginSpec,
+}
+
+/// Per-edge margins inside the trim, in inches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginSpec {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl Default for MarginSpec {
+    fn default() -> Self {
+        Self {
+            top: 0.5,
+            right: 0.5,
+            bottom: 0.5,
+            left: 0.5,
+        }
+    }
+}
+
+impl std::fmt::Display for MarginSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2}\" / {:.2}\" / {:.2}\" / {:.2}\"",
+            self.top, self.right, self.bottom, self.left
+        )
+    }
+}
+
+impl PhysicalSpec {
+    /// Typical trim, stock, and sides for `project_type`, set so the items
+    /// tile onto the stock with their bleed.
+    pub fn for_type(project_type: &ProjectType) -> Self {
+        let (item, stock, sides, quantity, margin) = match project_type {
+            ProjectType::Zine => ((5.5, 8.5), (12.0, 18.0), Sides::Double, 4, 0.375),
+            ProjectType::Poster => ((18.0, 24.0), (19.0, 25.0), Sides::Single, 1, 1.0),
+            ProjectType::Editorial => ((8.5, 11.0), (19.0, 25.0), Sides::Double, 4, 0.625),
+            ProjectType::CallingCards | ProjectType::Other(_) => {
+                ((3.07, 2.61), (11.0, 17.0), Sides::Double, 4, 0.5)
+            }
+        };
+        Self {
+            item_width_inches: item.0,
+            item_height_inches: item.1,
+            stock_width_inches: stock.0,
+            stock_height_inches: stock.1,
+            sides,
+            bleed_inches: 0.125,
+            quantity,
+            margins: MarginSpec {
+                top: margin,
+                right: margin,
+                bottom: margin,
+                left: margin,
+            },
+        }
+    }
+
+    /// How the item gangs up on the stock for printing.
+    pub fn imposition(&self) -> Imposition {
+        Imposition {
+            stock: PageSize::from_inches(self.stock_width_inches, self.stock_height_inches),
+            bleed: in_to_pt(self.bleed_inches),
+        }
+    }
+
+    /// How many items fit on one sheet of stock with their bleed and crop
+    /// marks, and how much stock is left over.
+    pub fn imposition_fit(&self) -> ImpositionReport {
+        let (item_w, item_h) = self.page_size().dimensions();
+        let imposition = self.imposition();
+        let (columns, rows) = imposition.fit(item_w, item_h);
+        let (sw, sh) = imposition.stock.dimensions();
+        let pitch = |item: f64| item + 2.0 * imposition.bleed;
+        ImpositionReport {
+            columns,
+            rows,
+            quantity: self.quantity,
+            leftover_width_inches: pt_to_in(sw - columns as f64 * pitch(item_w)),
+            leftover_height_inches: pt_to_in(sh - rows as f64 * pitch(item_h)),
+        }
+    }
+
+    /// The item's trim size as a layout page size, in points.
+    pub fn page_size(&self) -> PageSize {
+        PageSize::from_inches(self.item_width_inches, self.item_height_inches)
+    }
+
+    /// The spec's margins as a layout `Margin`, in points.
+    pub fn margin(&self) -> Margin {
+        Margin {
+            top: in_to_pt(self.margins.top),
+            right: in_to_pt(self.margins.right),
+            bottom: in_to_pt(self.margins.bottom),
+            left: in_to_pt(self.margins.left),
+        }
+    }
+}
+
+/// How a [`PhysicalSpec`]'s items tile onto its stock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpositionReport {
+    pub columns: u32,
+    pub rows: u32,
+    /// The quantity the spec asks for per sheet.
+    pub quantity: u32,
+    /// Stock not covered by the items and their bleeds, in inches; crop
+    /// marks go here.
+    pub leftover_width_inches: f64,
+    pub leftover_height_inches: f64,
+}
+
+impl ImpositionReport {
+    /// Items per sheet.
+    pub fn ups(&self) -> u32 {
+        self.columns.saturating_mul(self.rows)
+    }
+
+    /// Why the stock can't hold the job, if it can't.
+    pub fn warning(&self) -> Option<String> {
+        if self.ups() == 0 {
+            Some("the item and its bleed don't fit on the stock".to_string())
+        } else if self.ups() < self.quantity {
+            Some(format!(
+                "only {} of {} items fit on the stock",
+                self.ups(),
+                self.quantity
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for ImpositionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} x {} = {} up, {:.2}\" x {:.2}\" left over",
+            self.columns,
+            self.rows,
+            self.ups(),
+            self.leftover_width_inches,
+            self.leftover_height_inches
+        )
+    }
+}
+
+/// A stock-sized page laid out as the press sheet for `spec`: one grid cell
+/// per up, each the item plus its bleed, holding an empty `up-N` block, with
+/// crop marks at the trims. Feed it to `page_to_svg` or `page_to_latex` like
+/// any other page.
+pub fn impose(spec: &PhysicalSpec) -> Page {
+    let imposition = spec.imposition();
+    let stock = imposition.stock;
+    let (sw, sh) = stock.dimensions();
+    let (item_w, item_h) = spec.page_size().dimensions();
+    let (columns, rows) = imposition.fit(item_w, item_h);
+    if columns == 0 || rows == 0 {
+        return Page::new(1, stock, 1, 1);
+    }
+
+    let bleed = imposition.bleed;
+    let (cell_w, cell_h) = (item_w + 2.0 * bleed, item_h + 2.0 * bleed);
+    let side = |stock: f64, count: u32, cell: f64| (stock - count as f64 * cell) / 2.0;
+    let (x, y) = (side(sw, columns, cell_w), side(sh, rows, cell_h));
+    let margin = Margin {
+        top: y,
+        right: x,
+        bottom: y,
+        left: x,
+    };
+
+    let mut page = Page::new(1, stock, columns, rows);
+    page.grid = Grid::with_gutter(columns, rows, sw, sh, margin, 0.0);
+    page.blocks = page
+        .grid
+        .cells()
+        .enumerate()
+        .map(|(n, (col, row))| Block {
+            id: format!("up-{}", n + 1),
+            col,
+            row,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Empty,
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        })
+        .collect();
+    page.trim_marks = Some(TrimMarks {
+        trims: imposition.slots(item_w, item_h),
+        bleed,
+    });
+    page
+}
+
+/// The layout grid inside the margins: columns, rows, and the gutter
+/// between them in inches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridSpec {
+    pub columns: u32,
+    pub rows: u32,
+    pub gutter_inches: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl GridSpec {
+    /// A starting grid suited to `project_type`.
+    pub fn for_type(project_type: &ProjectType) -> Self {
+        let (columns, rows, gutter_inches) = match project_type {
+            ProjectType::CallingCards => (STARTER_GRID.0, STARTER_GRID.1, 0.125),
+            ProjectType::Zine => (2, 6, 0.167),
+            ProjectType::Poster => (12, 8, 0.25),
+            ProjectType::Editorial => (6, 9, 0.167),
+            ProjectType::Other(_) => (STARTER_GRID.0, STARTER_GRID.1, 0.167),
+        };
+        Self {
+            columns,
+            rows,
+            gutter_inches,
+        }
+    }
+}
+
+impl Default for GridSpec {
+    fn default() -> Self {
+        Self::for_type(&ProjectType::CallingCards)
+    }
+}
+
+impl std::fmt::Display for GridSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} x {}, {:.3}\" gutters",
+            self.columns, self.rows, self.gutter_inches
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Sides {
     Single,
@@ -74,7 +331,8 @@ impl Sides {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ColorSpec {
     pub mode: ColorMode,
     pub primary: CmykColor,
@@ -82,7 +340,7 @@ pub struct ColorSpec {
     pub spot_colors: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ColorMode {
     OneColor,
@@ -100,78 +358,102 @@ impl ColorMode {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CmykColor {
-    pub c: f64,
-    pub m: f64,
-    pub y: f64,
-    pub k: f64,
-    pub name: String,
+impl ColorSpec {
+    /// The usual color setup for `project_type`: posters and zines print in
+    /// one color, editorial in full process, everything else in two.
+    pub fn for_type(project_type: &ProjectType) -> Self {
+        let mode = match project_type {
+            ProjectType::Poster | ProjectType::Zine => ColorMode::OneColor,
+            ProjectType::Editorial => ColorMode::FullProcess,
+            ProjectType::CallingCards | ProjectType::Other(_) => ColorMode::TwoColor,
+        };
+        Self {
+            mode,
+            primary: CmykColor {
+                c: 0.0,
+                m: 0.0,
+                y: 0.0,
+                k: 100.0,
+                name: "Black".to_string(),
+            },
+            secondary: None,
+            spot_colors: Vec::new(),
+        }
+    }
 }
 
-impl std::fmt::Display for CmykColor {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} (C:{:.0} M:{:.0} Y:{:.0} K:{:.0})",
-            self.name, self.c, self.m, self.y, self.k
-        )
+impl From<&ColorSpec> for ColorPalette {
+    fn from(spec: &ColorSpec) -> Self {
+        let mut palette = ColorPalette::new();
+        palette.insert("primary", spec.primary.clone());
+        if let Some(secondary) = &spec.secondary {
+            palette.insert("secondary", secondary.clone());
+        }
+        for spot in &spec.spot_colors {
+            palette.add_spot(spot);
+        }
+        palette
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TypographySpec {
     pub primary_font: String,
     pub secondary_font: String,
     pub notes: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OutputSpec {
     pub formats: Vec<String>,
     pub impose: bool,
     pub delivery_notes: String,
+    /// Write `preview.svg` and `layout.tex` into `output/` when scaffolding;
+    /// off by default so a minimal scaffold leaves `output/` empty.
+    pub previews: bool,
 }
 
+/// Columns and rows of the default grid for small-format items.
+pub const STARTER_GRID: (u32, u32) = (4, 4);
+
 impl Default for ProjectSpec {
     fn default() -> Self {
         Self {
             name: String::new(),
             project_type: ProjectType::CallingCards,
-            physical: PhysicalSpec {
-                item_width_inches: 3.07,
-                item_height_inches: 2.61,
-                stock_width_inches: 11.0,
-                stock_height_inches: 17.0,
-                sides: Sides::Double,
-                bleed_inches: 0.125,
-                quantity: 4,
-            },
-            color: ColorSpec {
-                mode: ColorMode::TwoColor,
-                primary: CmykColor {
-                    c: 0.0,
-                    m: 0.0,
-                    y: 0.0,
-                    k: 100.0,
-                    name: "Black".to_string(),
-                },
-                secondary: None,
-                spot_colors: Vec::new(),
-            },
-            typography: TypographySpec {
-                primary_font: String::new(),
-                secondary_font: String::new(),
-                notes: String::new(),
-            },
+            physical: PhysicalSpec::default(),
+            grid: GridSpec::default(),
+            color: ColorSpec::default(),
+            typography: TypographySpec::default(),
             brief: String::new(),
             domains: vec!["design".to_string()],
             references: Vec::new(),
-            output: OutputSpec {
-                formats: vec!["pdf".to_string(), "svg".to_string(), "idml".to_string()],
-                impose: true,
-                delivery_notes: String::new(),
-            },
+            output: OutputSpec::default(),
+        }
+    }
+}
+
+impl Default for PhysicalSpec {
+    fn default() -> Self {
+        Self::for_type(&ProjectType::CallingCards)
+    }
+}
+
+impl Default for ColorSpec {
+    fn default() -> Self {
+        Self::for_type(&ProjectType::CallingCards)
+    }
+}
+
+impl Default for OutputSpec {
+    fn default() -> Self {
+        Self {
+            formats: vec!["pdf".to_string(), "svg".to_string(), "idml".to_string()],
+            impose: true,
+            delivery_notes: String::new(),
+            previews: false,
         }
     }
 }
@@ -190,13 +472,74 @@ impl ProjectSpec {
             .to_string()
     }
 
+    /// The layout grid described by the spec's trim size, margins, and grid.
+    pub fn layout_grid(&self) -> Grid {
+        let (w, h) = self.physical.page_size().dimensions();
+        let g = &self.grid;
+        let margin = self.physical.margin();
+        Grid::with_gutter(g.columns, g.rows, w, h, margin, in_to_pt(g.gutter_inches))
+    }
+
+    /// A blank first page for the item, on the spec's grid.
+    pub fn starter_page(&self) -> Page {
+        let mut page = Page::new(1, self.physical.page_size(), self.grid.columns, self.grid.rows);
+        page.grid = self.layout_grid();
+        page.palette = ColorPalette::from(&self.color);
+        if self.physical.sides == Sides::Double {
+            page.bind_facing();
+        }
+        page
+    }
+
+    /// Problems that would make the spec unrenderable; empty when valid.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let p = &self.physical;
+        if p.item_width_inches <= 0.0 || p.item_height_inches <= 0.0 {
+            problems.push("item size must be positive".to_string());
+        }
+        if p.stock_width_inches <= 0.0 || p.stock_height_inches <= 0.0 {
+            problems.push("stock size must be positive".to_string());
+        }
+        if p.bleed_inches < 0.0 {
+            problems.push("bleed can't be negative".to_string());
+        }
+        let m = &p.margins;
+        if m.left + m.right >= p.item_width_inches || m.top + m.bottom >= p.item_height_inches {
+            problems.push("margins leave no live area inside the item".to_string());
+        }
+        if self.grid.columns == 0 || self.grid.rows == 0 {
+            problems.push("grid needs at least one column and one row".to_string());
+        } else if self.grid.gutter_inches < 0.0 {
+            problems.push("gutter can't be negative".to_string());
+        } else {
+            let grid = self.layout_grid();
+            if grid.column_width <= 0.0 || grid.row_height <= 0.0 {
+                problems.push("grid has no room for its cells".to_string());
+            }
+        }
+        problems
+    }
+
+    /// Parse a possibly hand-edited `project.yaml`: absent fields take their
+    /// defaults, and unrecognized keys are reported (as dotted paths) rather
+    /// than rejected.
+    pub fn from_yaml_lenient(yaml: &str) -> Result<(ProjectSpec, Vec<String>), serde_yaml::Error> {
+        let value: serde_yaml::Value = serde_yaml::from_str(yaml)?;
+        let known = serde_yaml::to_value(ProjectSpec::default())?;
+        let mut warnings = Vec::new();
+        collect_unknown_keys(&value, &known, "", &mut warnings);
+        let spec = serde_yaml::from_value(value)?;
+        Ok((spec, warnings))
+    }
+
     pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
         serde_yaml::to_string(self)
     }
 
     pub fn brief_md(&self) -> String {
         format!(
-            "# {}\n\n## Creative Brief\n\n{}\n\n## Physical Specs\n\n- Item: {:.2}\" x {:.2}\"\n- Stock: {:.0}\" x {:.0}\"\n- Sides: {}\n- Bleed: {:.3}\"\n- Quantity: {}\n\n## Color\n\n- Mode: {}\n- Primary: {}\n{}\n\n## Typography\n\n- Primary: {}\n- Secondary: {}\n{}\n",
+            "# {}\n\n## Creative Brief\n\n{}\n\n## Physical Specs\n\n- Item: {:.2}\" x {:.2}\"\n- Stock: {:.0}\" x {:.0}\"\n- Sides: {}\n- Bleed: {:.3}\"\n- Margins: {}\n- Grid: {}\n- Quantity: {}\n\n## Color\n\n- Mode: {}\n- Primary: {}\n{}\n\n## Typography\n\n- Primary: {}\n- Secondary: {}\n{}\n",
             self.name,
             self.brief,
             self.physical.item_width_inches,
@@ -205,13 +548,551 @@ impl ProjectSpec {
             self.physical.stock_height_inches,
             self.physical.sides.label(),
             self.physical.bleed_inches,
+            self.physical.margins,
+            self.grid,
             self.physical.quantity,
             self.color.mode.label(),
             self.color.primary,
-            self.color.secondary.as_ref().map_or(String::new(), |c| format!("- Secondary: {c}")),
+            self.color
+                .secondary
+                .iter()
+                .map(|c| format!("- Secondary: {c}"))
+                .chain((!self.color.spot_colors.is_empty()).then(|| {
+                    format!("- Spot colors: {}", self.color.spot_colors.join(", "))
+                }))
+                .collect::<Vec<_>>()
+                .join("\n"),
             self.typography.primary_font,
             self.typography.secondary_font,
             if self.typography.notes.is_empty() { String::new() } else { format!("- Notes: {}", self.typography.notes) },
         )
     }
 }
+
+/// Walk `value` alongside the default spec's shape, noting keys the spec
+/// doesn't have. Only struct-like mappings are compared.
+fn collect_unknown_keys(
+    value: &serde_yaml::Value,
+    known: &serde_yaml::Value,
+    prefix: &str,
+    warnings: &mut Vec<String>,
+) {
+    let (Some(value), Some(known)) = (value.as_mapping(), known.as_mapping()) else {
+        return;
+    };
+    for (key, child) in value {
+        let name = key.as_str().unwrap_or("?");
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match known.get(key) {
+            Some(known_child) => collect_unknown_keys(child, known_child, &path, warnings),
+            None => warnings.push(format!("unknown field `{path}`")),
+        }
+    }
+}
+
+/// A single step of progress reported while scaffolding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaffoldEvent {
+    DirCreated(String),
+    FileWritten(String),
+    ReferenceCopied { from: String, to: String },
+}
+
+impl std::fmt::Display for ScaffoldEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DirCreated(path) => write!(f, "created {path}/"),
+            Self::FileWritten(path) => write!(f, "wrote {path}"),
+            Self::ReferenceCopied { from, to } => write!(f, "copied {from} -> {to}"),
+        }
+    }
+}
+
+/// Scaffold `spec`'s project directory, refusing to touch one that already
+/// exists. Returns the directory. This is [`scaffold_spec_with`] with a
+/// no-op progress callback, and stands in for the wizard's old plain
+/// `scaffold`.
+pub fn scaffold_spec(spec: &ProjectSpec) -> std::io::Result<String> {
+    scaffold_spec_with(spec, false, |_| {})
+}
+
+/// Scaffold `spec`'s project directory, reporting each created directory,
+/// written file, and copied reference to `progress` as it happens.
+/// Unless `overwrite` is set, an existing directory is an
+/// [`AlreadyExists`](std::io::ErrorKind::AlreadyExists) error and nothing
+/// is written, so hand-edited notes and decisions survive a re-run.
+pub fn scaffold_spec_with<F: FnMut(ScaffoldEvent)>(
+    spec: &ProjectSpec,
+    overwrite: bool,
+    mut progress: F,
+) -> std::io::Result<String> {
+    let dir = spec.scaffold_dir();
+    if !overwrite && Path::new(&dir).exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{dir}/ already exists"),
+        ));
+    }
+    for sub in ["cards/front", "cards/back", "reference", "moodboard", "output"] {
+        let path = format!("{dir}/{sub}");
+        std::fs::create_dir_all(&path)?;
+        progress(ScaffoldEvent::DirCreated(path));
+    }
+
+    let yaml = spec.to_yaml().map_err(std::io::Error::other)?;
+    let brief = spec.brief_md();
+    let notes = format!("# Design Notes: {}\n", spec.name);
+    let files = [
+        ("project.yaml", yaml.as_str()),
+        ("brief.md", brief.as_str()),
+        ("decisions.json", "[]"),
+        ("design-notes.md", notes.as_str()),
+    ];
+    for (name, contents) in files {
+        let path = format!("{dir}/{name}");
+        std::fs::write(&path, contents)?;
+        progress(ScaffoldEvent::FileWritten(path));
+    }
+
+    if spec.output.previews {
+        let page = spec.starter_page();
+        let previews = [
+            ("output/preview.svg", grids_layout::render::page_to_svg(&page)),
+            ("output/layout.tex", grids_layout::render::page_to_latex(&page)),
+        ];
+        for (name, contents) in previews {
+            let path = format!("{dir}/{name}");
+            std::fs::write(&path, contents)?;
+            progress(ScaffoldEvent::FileWritten(path));
+        }
+    }
+
+    for reference in &spec.references {
+        let src = Path::new(reference);
+        let Some(file_name) = src.file_name().filter(|_| src.is_file()) else {
+            continue;
+        };
+        let dest = format!("{dir}/reference/{}", file_name.to_string_lossy());
+        std::fs::copy(src, &dest)?;
+        progress(ScaffoldEvent::ReferenceCopied {
+            from: reference.clone(),
+            to: dest,
+        });
+    }
+
+    Ok(dir)
+}
+
+/// A scaffolded project found on disk.
+#[derive(Debug, Clone)]
+pub struct ProjectSummary {
+    pub dir: PathBuf,
+    pub name: String,
+    pub project_type: String,
+    pub modified: chrono::DateTime<chrono::Local>,
+}
+
+/// Scan `root` for project directories containing a readable `project.yaml`,
+/// newest first. Entries that can't be read or parsed are skipped.
+pub fn list_projects(root: &Path) -> Vec<ProjectSummary> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut projects: Vec<ProjectSummary> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let spec_path = entry.path().join("project.yaml");
+            let yaml = std::fs::read_to_string(&spec_path).ok()?;
+            let (spec, _) = ProjectSpec::from_yaml_lenient(&yaml).ok()?;
+            let modified = std::fs::metadata(&spec_path).ok()?.modified().ok()?;
+            Some(ProjectSummary {
+                dir: entry.path(),
+                name: spec.name,
+                project_type: spec.project_type.label().to_string(),
+                modified: modified.into(),
+            })
+        })
+        .collect();
+
+    projects.sort_by_key(|p| std::cmp::Reverse(p.modified));
+    projects
+}
+
+/// Per-user settings directory, `~/.grids`; `None` without a home directory.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".grids"))
+}
+
+/// Where the intake wizard keeps saved drafts.
+pub fn drafts_dir() -> Option<PathBuf> {
+    Some(config_dir()?.join("drafts"))
+}
+
+/// The domain selection saved by [`save_default_domains`], if any.
+pub fn load_default_domains() -> Option<Vec<String>> {
+    let text = std::fs::read_to_string(config_dir()?.join("domains")).ok()?;
+    Some(
+        text.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Remember `domains`, one per line, as the selection for the next project.
+pub fn save_default_domains(domains: &[String]) -> std::io::Result<()> {
+    let dir = config_dir().ok_or_else(|| std::io::Error::other("no home directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("domains"), domains.join("\n") + "\n")
+}
+
+/// File extensions picked up as reference material by [`scan_references`].
+pub const REFERENCE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "tif", "tiff", "webp", "svg", "pdf",
+];
+
+/// Image and PDF files in `dir`, sorted, descending into subdirectories when
+/// `recursive`. Hidden files and directories are skipped.
+pub fn scan_references(dir: &Path, recursive: bool) -> std::io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            if recursive {
+                found.extend(scan_references(&path, true)?);
+            }
+            continue;
+        }
+        let relevant = path.extension().is_some_and(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            REFERENCE_EXTENSIONS.contains(&ext.as_str())
+        });
+        if relevant {
+            found.push(path);
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Pages from the project's `pages.json` when present, otherwise the spec's
+/// blank starter page.
+pub fn load_pages(dir: &Path, spec: &ProjectSpec) -> Result<Vec<Page>, String> {
+    let path = dir.join("pages.json");
+    if !path.exists() {
+        return Ok(vec![spec.starter_page()]);
+    }
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("reading pages.json: {e}"))?;
+    let mut pages: Vec<Page> =
+        serde_json::from_str(&json).map_err(|e| format!("parsing pages.json: {e}"))?;
+    if spec.physical.sides == Sides::Double {
+        for page in &mut pages {
+            page.bind_facing();
+        }
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grids_layout::render::page_to_latex;
+
+    /// A spec named uniquely for `test`, so tests scaffolding under
+    /// `projects/` don't collide.
+    fn scratch_spec(test: &str) -> ProjectSpec {
+        ProjectSpec {
+            name: format!("{test} {}", std::process::id()),
+            ..ProjectSpec::default()
+        }
+    }
+
+    fn remove_scaffold(dir: &str) {
+        std::fs::remove_dir_all(dir).unwrap();
+        let _ = std::fs::remove_dir("projects");
+    }
+
+    #[test]
+    fn scaffold_writes_previews_when_asked() {
+        let mut spec = scratch_spec("previews");
+        spec.output.previews = true;
+        let dir = scaffold_spec(&spec).unwrap();
+        let svg = std::fs::read_to_string(format!("{dir}/output/preview.svg"));
+        let tex = Path::new(&dir).join("output/layout.tex").is_file();
+        remove_scaffold(&dir);
+
+        let svg = svg.unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(tex);
+    }
+
+    #[test]
+    fn scaffold_reports_each_artifact_once_in_order() {
+        let reference = std::env::temp_dir().join(format!("grids-ref-{}.png", std::process::id()));
+        std::fs::write(&reference, b"png").unwrap();
+        let mut spec = scratch_spec("progress");
+        spec.references = vec![reference.display().to_string()];
+
+        let mut events = Vec::new();
+        let dir = scaffold_spec_with(&spec, false, |e| events.push(e)).unwrap();
+        remove_scaffold(&dir);
+        std::fs::remove_file(&reference).unwrap();
+
+        let subdirs = ["cards/front", "cards/back", "reference", "moodboard", "output"];
+        let mut expected: Vec<ScaffoldEvent> = subdirs
+            .iter()
+            .map(|sub| ScaffoldEvent::DirCreated(format!("{dir}/{sub}")))
+            .collect();
+        expected.extend(
+            ["project.yaml", "brief.md", "decisions.json", "design-notes.md"]
+                .iter()
+                .map(|name| ScaffoldEvent::FileWritten(format!("{dir}/{name}"))),
+        );
+        let file_name = reference.file_name().unwrap().to_string_lossy();
+        expected.push(ScaffoldEvent::ReferenceCopied {
+            from: reference.display().to_string(),
+            to: format!("{dir}/reference/{file_name}"),
+        });
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn scaffold_leaves_output_empty_by_default() {
+        let spec = scratch_spec("no previews");
+        let dir = scaffold_spec(&spec).unwrap();
+        let entries = std::fs::read_dir(format!("{dir}/output")).unwrap().count();
+        remove_scaffold(&dir);
+        assert_eq!(entries, 0);
+    }
+
+    #[test]
+    fn palette_from_two_color_spec_resolves_both_names() {
+        let spec = ColorSpec {
+            secondary: Some(CmykColor {
+                c: 0.0,
+                m: 100.0,
+                y: 100.0,
+                k: 0.0,
+                name: "Red".to_string(),
+            }),
+            spot_colors: vec!["PMS 185 C".to_string()],
+            ..ColorSpec::for_type(&ProjectType::CallingCards)
+        };
+        let palette = ColorPalette::from(&spec);
+        assert_eq!(palette.get("primary"), Some(&spec.primary));
+        assert_eq!(palette.to_rgb_hex("primary").as_deref(), Some("#000000"));
+        assert_eq!(palette.to_rgb_hex("secondary").as_deref(), Some("#ff0000"));
+        assert!(palette.is_spot("PMS 185 C"));
+    }
+
+    #[test]
+    fn fills_render_their_palette_color() {
+        let mut spec = ProjectSpec::default();
+        spec.color.primary.k = 0.0;
+        spec.color.primary.c = 100.0;
+        let mut page = spec.starter_page();
+        page.add_block(Block {
+            id: "swatch".to_string(),
+            col: 0,
+            row: 0,
+            col_span: 1,
+            row_span: 1,
+            content: BlockContent::Fill {
+                color: CmykColor {
+                    c: 0.0,
+                    m: 0.0,
+                    y: 0.0,
+                    k: 0.0,
+                    name: "Paper".to_string(),
+                },
+                color_ref: Some("primary".to_string()),
+            },
+            decision_ids: Vec::new(),
+            clip: false,
+            corner_radius: 0.0,
+            rotation: 0.0,
+        });
+        let svg = grids_layout::render::page_to_svg(&page);
+        assert!(svg.contains("fill=\"#00ffff\""));
+        assert!(!svg.contains("fill=\"#ffffff\"/>"));
+    }
+
+    #[test]
+    fn custom_margins_flow_into_the_grid() {
+        let mut spec = ProjectSpec::default();
+        spec.physical.margins = MarginSpec {
+            top: 0.25,
+            right: 0.5,
+            bottom: 1.0,
+            left: 0.75,
+        };
+        let m = spec.layout_grid().margin;
+        assert_eq!((m.top, m.right, m.bottom, m.left), (18.0, 36.0, 72.0, 54.0));
+        assert_eq!(spec.starter_page().grid.cell_origin(0, 0), (54.0, 18.0));
+    }
+
+    #[test]
+    fn grid_spec_round_trips_through_yaml_into_the_grid() {
+        let spec = ProjectSpec {
+            name: "Grid".to_string(),
+            grid: GridSpec {
+                columns: 3,
+                rows: 5,
+                gutter_inches: 0.25,
+            },
+            ..ProjectSpec::default()
+        };
+        let yaml = spec.to_yaml().unwrap();
+        let (loaded, warnings) = ProjectSpec::from_yaml_lenient(&yaml).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(loaded.grid, spec.grid);
+
+        let grid = loaded.layout_grid();
+        let (w, _) = loaded.physical.page_size().dimensions();
+        let m = loaded.physical.margin();
+        assert_eq!((grid.columns, grid.rows), (3, 5));
+        assert_eq!((grid.gutter_h, grid.gutter_v), (18.0, 18.0));
+        let expected_w = (w - m.left - m.right - 2.0 * 18.0) / 3.0;
+        assert!((grid.column_width - expected_w).abs() < 1e-9);
+    }
+
+    #[test]
+    fn impose_places_one_block_per_up() {
+        let spec = PhysicalSpec::for_type(&ProjectType::CallingCards);
+        let report = spec.imposition_fit();
+        let page = impose(&spec);
+
+        assert_eq!((page.grid.columns, page.grid.rows), (report.columns, report.rows));
+        let ids: Vec<String> = page.blocks.iter().map(|b| b.id.clone()).collect();
+        let expected: Vec<String> = (1..=report.ups()).map(|n| format!("up-{n}")).collect();
+        assert_eq!(ids, expected);
+        let marks = page.trim_marks.as_ref().unwrap();
+        assert_eq!(marks.trims.len() as u32, report.ups());
+
+        // Each trim sits inside its cell, one bleed in from every edge.
+        let cell = page.grid.cell_rect(0, 0, 1, 1);
+        let trim = marks.trims[0];
+        assert!((trim.x - cell.x - marks.bleed).abs() < 1e-9);
+        assert!((trim.y - cell.y - marks.bleed).abs() < 1e-9);
+
+        assert!(page_to_latex(&page).contains("\\draw[line width="));
+    }
+
+    #[test]
+    fn impose_without_room_is_an_empty_sheet() {
+        let spec = PhysicalSpec {
+            stock_width_inches: 2.0,
+            stock_height_inches: 2.0,
+            ..PhysicalSpec::for_type(&ProjectType::Poster)
+        };
+        let page = impose(&spec);
+        assert!(page.blocks.is_empty());
+        assert!(page.trim_marks.is_none());
+    }
+
+    #[test]
+    fn list_projects_skips_folders_without_a_valid_spec() {
+        let root = std::env::temp_dir().join(format!("grids-recent-{}", std::process::id()));
+        let write = |dir: &str, yaml: &str, age_secs: u64| {
+            let path = root.join(dir).join("project.yaml");
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, yaml).unwrap();
+            let then = std::time::SystemTime::now() - std::time::Duration::from_secs(age_secs);
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(then).unwrap();
+        };
+        let spec = |name: &str, project_type: ProjectType| {
+            let spec = ProjectSpec {
+                name: name.to_string(),
+                project_type,
+                ..ProjectSpec::default()
+            };
+            spec.to_yaml().unwrap()
+        };
+        write("older", &spec("Older Zine", ProjectType::Zine), 3600);
+        write("newer", &spec("Newer Poster", ProjectType::Poster), 60);
+        write("broken", "name: [unterminated", 0);
+        std::fs::create_dir_all(root.join("no-spec")).unwrap();
+
+        let found = list_projects(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let names: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Newer Poster", "Older Zine"]);
+        assert_eq!(found[0].project_type, ProjectType::Poster.label());
+        assert_eq!(found[1].dir, root.join("older"));
+    }
+
+    #[test]
+    fn partial_yaml_fills_the_rest_from_defaults() {
+        let yaml = "\
+name: Spring Zine
+physical:
+  item_width_inches: 5.5
+  item_height_inches: 8.5
+  stock_width_inches: 11
+  stock_height_inches: 17
+  sides: double
+  bleed_inches: 0.125
+  quantity: 200
+  paper: uncoated
+";
+        let (spec, warnings) = ProjectSpec::from_yaml_lenient(yaml).unwrap();
+        assert_eq!(warnings, ["unknown field `physical.paper`"]);
+        assert_eq!(spec.name, "Spring Zine");
+        assert_eq!(spec.physical.item_width_inches, 5.5);
+        assert_eq!(spec.physical.sides, Sides::Double);
+        assert_eq!(spec.physical.margins, MarginSpec::default());
+
+        let defaults = ProjectSpec::default();
+        assert_eq!(spec.project_type, defaults.project_type);
+        assert_eq!(spec.grid, defaults.grid);
+        assert_eq!(spec.color, defaults.color);
+        assert_eq!(spec.output, defaults.output);
+        assert!(spec.brief.is_empty());
+    }
+
+    #[test]
+    fn scan_references_keeps_images_and_pdfs_only() {
+        let root = std::env::temp_dir().join(format!("grids-refs-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        for file in [
+            "poster.JPG",
+            "brief.pdf",
+            "notes.txt",
+            "archive.zip",
+            "logo.svg",
+            ".hidden.png",
+            "nested/detail.png",
+        ] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+
+        let names = |paths: Vec<PathBuf>| -> Vec<String> {
+            paths
+                .iter()
+                .map(|p| p.strip_prefix(&root).unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+        let flat = scan_references(&root, false).map(names);
+        let deep = scan_references(&root, true).map(names);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(flat.unwrap(), ["brief.pdf", "logo.svg", "poster.JPG"]);
+        assert_eq!(
+            deep.unwrap(),
+            ["brief.pdf", "logo.svg", "nested/detail.png", "poster.JPG"]
+        );
+    }
+}
@@ -6,6 +6,12 @@ use ratatui::{
 use crate::wizard::{Step, Wizard};
 
 pub fn draw(frame: &mut Frame, wizard: &Wizard) {
+    draw_with_status(frame, wizard, None);
+}
+
+/// Draw the wizard with `status` replacing the footer help, used for live
+/// progress while a long-running action (scaffolding) is in flight.
+pub fn draw_with_status(frame: &mut Frame, wizard: &Wizard, status: Option<&str>) {
     let outer = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -17,7 +23,7 @@ pub fn draw(frame: &mut Frame, wizard: &Wizard) {
 
     draw_header(frame, outer[0], wizard);
     draw_step(frame, outer[1], wizard);
-    draw_footer(frame, outer[2], wizard);
+    draw_footer(frame, outer[2], wizard, status);
 }
 
 fn draw_header(frame: &mut Frame, area: Rect, wizard: &Wizard) {
@@ -50,19 +56,44 @@ fn draw_header(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     frame.render_widget(p, area);
 }
 
-fn draw_footer(frame: &mut Frame, area: Rect, wizard: &Wizard) {
-    let help = if wizard.step == Step::Review {
-        " Enter: scaffold project | Backspace: go back | q: quit "
+fn draw_footer(frame: &mut Frame, area: Rect, wizard: &Wizard, status: Option<&str>) {
+    let help = if wizard.step == Step::Review && wizard.editing.is_some() {
+        " Enter: save changes | Esc: back step | q: quit "
+    } else if wizard.step == Step::Review {
+        " Enter: scaffold project | Esc: back step | q: quit "
+    } else if wizard.step == Step::Color && wizard.field_index == 2 {
+        " Del: add/clear secondary | Tab: next field | Enter: next step | Esc: back step "
+    } else if wizard.step == Step::Color && wizard.field_index == 3 {
+        " Enter: add spot color | Up/Down: select | Del: remove | Tab: next field | Esc: back step "
+    } else if wizard.step == Step::Color && wizard.field_index == 1 {
+        " Type: name C M Y K (0-100) | Tab: next field | Enter: next step | Esc: back step "
+    } else if wizard.step == Step::Grid || wizard.step == Step::Physical {
+        " Tab: next field | Enter: next step | Esc: back step | q: quit "
+    } else if wizard.step == Step::References {
+        " Ctrl+O: add files from dir on last line | Alt+O: recursively | Tab: next step | Esc: back step "
+    } else if wizard.step == Step::Output && wizard.field_index < 2 {
+        " Space: toggle | Up/Down: move | Tab: next field | Enter: next step | Esc: back step "
+    } else if wizard.on_new_domain() {
+        " Type a name | Enter: add it, or next step when empty | Up: back to list "
     } else if wizard.step == Step::Domains {
-        " Space: toggle | Tab: next field | Enter: next step | Backspace: back | q: quit "
+        " Space: toggle | Tab: next field | Enter: next step | Esc: back step | q: quit "
+    } else if wizard.step == Step::Brief {
+        " Enter: new line | Tab: next step | Esc: back step | Ctrl+C: quit "
+    } else {
+        " Tab: next field | Enter: next step | Esc: back step | Ctrl+C: quit "
+    };
+    let (text, color) = if let Some(err) = &wizard.field_error {
+        (format!(" {err} "), Color::Red)
+    } else if let Some(status) = status.or(wizard.notice.as_deref()) {
+        (format!(" {status} "), Color::Yellow)
     } else {
-        " Tab: next field | Enter: next step | Backspace: back step | q: quit "
+        (help.to_string(), Color::DarkGray)
     };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
-    let p = Paragraph::new(help)
-        .style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(color));
+    let p = Paragraph::new(text)
+        .style(Style::default().fg(color))
         .block(block);
     frame.render_widget(p, area);
 }
@@ -79,6 +110,7 @@ fn draw_step(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     match wizard.step {
         Step::Name => draw_name(frame, inner, wizard),
         Step::Physical => draw_physical(frame, inner, wizard),
+        Step::Grid => draw_grid(frame, inner, wizard),
         Step::Color => draw_color(frame, inner, wizard),
         Step::Typography => draw_typography(frame, inner, wizard),
         Step::Brief => draw_brief(frame, inner, wizard),
@@ -114,7 +146,7 @@ fn draw_name(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     };
     frame.render_widget(Paragraph::new(name_text).block(name_block), chunks[0]);
 
-    let type_items: Vec<ListItem> = crate::project::ProjectType::VARIANTS
+    let type_items: Vec<ListItem> = grids_intake::project::ProjectType::VARIANTS
         .iter()
         .enumerate()
         .map(|(i, v)| {
@@ -143,31 +175,36 @@ fn draw_physical(frame: &mut Frame, area: Rect, wizard: &Wizard) {
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
             Constraint::Min(0),
         ])
         .split(area);
 
     let p = &wizard.spec.physical;
+    // The focused text field shows what's being typed; the rest show the spec.
+    let editing = |i: usize, value: String| {
+        if wizard.field_index == i {
+            format!("{}|", wizard.input_buf)
+        } else {
+            value
+        }
+    };
 
     let item_block = Block::default()
-        .title(" Item Size (inches) ")
+        .title(" Item Size (inches: width x height) ")
         .borders(Borders::ALL)
         .border_style(field_style(wizard.field_index == 0));
-    frame.render_widget(
-        Paragraph::new(format!("{:.2}\" x {:.2}\"", p.item_width_inches, p.item_height_inches))
-            .block(item_block),
-        chunks[0],
-    );
+    let item = format!("{:.2}\" x {:.2}\"", p.item_width_inches, p.item_height_inches);
+    let item = editing(0, item);
+    frame.render_widget(Paragraph::new(item).block(item_block), chunks[0]);
 
     let stock_block = Block::default()
-        .title(" Stock Size (inches) ")
+        .title(" Stock Size (inches: width x height) ")
         .borders(Borders::ALL)
         .border_style(field_style(wizard.field_index == 1));
-    frame.render_widget(
-        Paragraph::new(format!("{:.0}\" x {:.0}\"", p.stock_width_inches, p.stock_height_inches))
-            .block(stock_block),
-        chunks[1],
-    );
+    let stock = format!("{:.0}\" x {:.0}\"", p.stock_width_inches, p.stock_height_inches);
+    let stock = editing(1, stock);
+    frame.render_widget(Paragraph::new(stock).block(stock_block), chunks[1]);
 
     let sides_labels = ["single-sided", "double-sided"];
     let sides_str: String = sides_labels
@@ -188,21 +225,72 @@ fn draw_physical(frame: &mut Frame, area: Rect, wizard: &Wizard) {
         .border_style(field_style(wizard.field_index == 2));
     frame.render_widget(Paragraph::new(sides_str).block(sides_block), chunks[2]);
 
-    let extra = format!(
-        "Bleed: {:.3}\"    Quantity: {}",
-        p.bleed_inches, p.quantity
-    );
+    let extra = format!("Bleed: {:.3}\"    Quantity: {}", p.bleed_inches, p.quantity);
+    let extra = editing(3, extra);
     let extra_block = Block::default()
-        .title(" Bleed & Quantity ")
+        .title(" Bleed & Quantity (inches, count) ")
         .borders(Borders::ALL)
         .border_style(field_style(wizard.field_index == 3));
     frame.render_widget(Paragraph::new(extra).block(extra_block), chunks[3]);
+
+    let margins_block = Block::default()
+        .title(" Margins (inches: top right bottom left) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(wizard.field_index == 4));
+    let margins = editing(4, p.margins.to_string());
+    frame.render_widget(Paragraph::new(margins).block(margins_block), chunks[4]);
+}
+
+fn draw_grid(frame: &mut Frame, area: Rect, wizard: &Wizard) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let g = &wizard.spec.grid;
+    let labels = ["Columns", "Rows", "Gutter (inches)"];
+    let values = [
+        g.columns.to_string(),
+        g.rows.to_string(),
+        format!("{:.3}\"", g.gutter_inches),
+    ];
+
+    for (i, (chunk, (label, val))) in chunks
+        .iter()
+        .zip(labels.iter().zip(values.iter()))
+        .enumerate()
+    {
+        let block = Block::default()
+            .title(format!(" {label} "))
+            .borders(Borders::ALL)
+            .border_style(field_style(wizard.field_index == i));
+        let text = if wizard.field_index == i {
+            format!("{}|", wizard.input_buf)
+        } else {
+            val.to_string()
+        };
+        frame.render_widget(Paragraph::new(text).block(block), *chunk);
+    }
+
+    let (w, h) = wizard.spec.physical.page_size().dimensions();
+    let grid = wizard.spec.layout_grid();
+    let summary = format!(
+        "Cell: {:.1}pt x {:.1}pt on a {:.1}pt x {:.1}pt page",
+        grid.column_width, grid.row_height, w, h
+    );
+    frame.render_widget(Paragraph::new(summary), chunks[3]);
 }
 
 fn draw_color(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Min(0),
@@ -229,27 +317,59 @@ fn draw_color(frame: &mut Frame, area: Rect, wizard: &Wizard) {
         .border_style(field_style(wizard.field_index == 0));
     frame.render_widget(Paragraph::new(mode_str).block(mode_block), chunks[0]);
 
-    let primary = &wizard.spec.color.primary;
+    let primary = if wizard.field_index == 1 {
+        format!("{}|", wizard.input_buf)
+    } else {
+        wizard.spec.color.primary.to_string()
+    };
     let primary_block = Block::default()
-        .title(" Primary Color ")
+        .title(" Primary Color (name C M Y K) ")
         .borders(Borders::ALL)
         .border_style(field_style(wizard.field_index == 1));
-    frame.render_widget(
-        Paragraph::new(format!("{primary}")).block(primary_block),
-        chunks[1],
-    );
+    frame.render_widget(Paragraph::new(primary).block(primary_block), chunks[1]);
 
-    let sec = wizard
-        .spec
-        .color
-        .secondary
-        .as_ref()
-        .map_or("(none)".to_string(), |c| format!("{c}"));
+    let sec = if wizard.field_index == 2 {
+        format!("{}|", wizard.input_buf)
+    } else {
+        wizard
+            .spec
+            .color
+            .secondary
+            .as_ref()
+            .map_or("(none)".to_string(), |c| format!("{c}"))
+    };
     let sec_block = Block::default()
-        .title(" Secondary Color ")
+        .title(" Secondary Color (name C M Y K, empty for none) ")
         .borders(Borders::ALL)
         .border_style(field_style(wizard.field_index == 2));
     frame.render_widget(Paragraph::new(sec).block(sec_block), chunks[2]);
+
+    let active = wizard.field_index == 3;
+    let mut spot_items: Vec<ListItem> = wizard
+        .spec
+        .color
+        .spot_colors
+        .iter()
+        .enumerate()
+        .map(|(i, spot)| {
+            let selected = active && i == wizard.spot_index;
+            let marker = if selected { "> " } else { "  " };
+            let style = if selected {
+                Style::default().fg(Color::Cyan).bold()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{marker}{spot}")).style(style)
+        })
+        .collect();
+    if active {
+        spot_items.push(ListItem::new(format!("+ {}|", wizard.input_buf)));
+    }
+    let spot_block = Block::default()
+        .title(" Spot Colors (e.g. PMS 485 C) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(active));
+    frame.render_widget(List::new(spot_items).block(spot_block), chunks[3]);
 }
 
 fn draw_typography(frame: &mut Frame, area: Rect, wizard: &Wizard) {
@@ -287,12 +407,19 @@ fn draw_typography(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     }
 }
 
+/// The multiline input with a `|` marking the cursor.
+fn with_cursor(wizard: &Wizard) -> String {
+    let cursor = wizard.cursor.min(wizard.input_buf.len());
+    let (before, after) = wizard.input_buf.split_at(cursor);
+    format!("{before}|{after}")
+}
+
 fn draw_brief(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     let block = Block::default()
-        .title(" Creative Brief (type freely, Enter to submit) ")
+        .title(" Creative Brief (type freely, Tab to submit) ")
         .borders(Borders::ALL)
         .border_style(field_style(true));
-    let text = format!("{}|", wizard.input_buf);
+    let text = with_cursor(wizard);
     frame.render_widget(
         Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
         area,
@@ -300,7 +427,7 @@ fn draw_brief(frame: &mut Frame, area: Rect, wizard: &Wizard) {
 }
 
 fn draw_domains(frame: &mut Frame, area: Rect, wizard: &Wizard) {
-    let items: Vec<ListItem> = wizard
+    let mut items: Vec<ListItem> = wizard
         .available_domains()
         .iter()
         .enumerate()
@@ -315,6 +442,11 @@ fn draw_domains(frame: &mut Frame, area: Rect, wizard: &Wizard) {
             ListItem::new(format!("{marker}{check} {d}")).style(style)
         })
         .collect();
+    items.push(if wizard.on_new_domain() {
+        ListItem::new(format!("> + {}|", wizard.input_buf)).style(field_style(true))
+    } else {
+        ListItem::new("  + add a domain")
+    });
 
     let block = Block::default()
         .title(" Domain Configs (Space to toggle) ")
@@ -327,7 +459,7 @@ fn draw_references(frame: &mut Frame, area: Rect, wizard: &Wizard) {
         .title(" Reference Paths (one per line) ")
         .borders(Borders::ALL)
         .border_style(field_style(true));
-    let text = format!("{}|", wizard.input_buf);
+    let text = with_cursor(wizard);
     frame.render_widget(
         Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
         area,
@@ -335,28 +467,53 @@ fn draw_references(frame: &mut Frame, area: Rect, wizard: &Wizard) {
 }
 
 fn draw_output(frame: &mut Frame, area: Rect, wizard: &Wizard) {
+    let formats = wizard.output_formats();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(formats.len() as u16 + 2),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
         .split(area);
 
-    let formats = wizard.spec.output.formats.join(", ");
+    let format_items: Vec<ListItem> = formats
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let check = if wizard.format_toggles[i] { "[x]" } else { "[ ]" };
+            let selected = wizard.field_index == 0 && i == wizard.format_index;
+            let marker = if selected { "> " } else { "  " };
+            let style = if selected {
+                Style::default().fg(Color::Cyan).bold()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{marker}{check} {f}")).style(style)
+        })
+        .collect();
     let fmt_block = Block::default()
-        .title(" Formats ")
-        .borders(Borders::ALL);
-    frame.render_widget(Paragraph::new(formats).block(fmt_block), chunks[0]);
+        .title(" Formats (Space to toggle) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(wizard.field_index == 0));
+    frame.render_widget(List::new(format_items).block(fmt_block), chunks[0]);
 
-    let impose = if wizard.spec.output.impose { "Yes" } else { "No" };
+    let impose = if wizard.spec.output.impose { "[x] Yes" } else { "[ ] No" };
     let imp_block = Block::default()
-        .title(" Impose on stock? ")
-        .borders(Borders::ALL);
+        .title(" Impose on stock? (Space to toggle) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(wizard.field_index == 1));
     frame.render_widget(Paragraph::new(impose).block(imp_block), chunks[1]);
 
     let notes_block = Block::default()
         .title(" Delivery Notes ")
         .borders(Borders::ALL)
-        .border_style(field_style(true));
-    let text = format!("{}|", wizard.input_buf);
+        .border_style(field_style(wizard.field_index == 2));
+    let text = if wizard.field_index == 2 {
+        format!("{}|", wizard.input_buf)
+    } else {
+        wizard.spec.output.delivery_notes.clone()
+    };
     frame.render_widget(
         Paragraph::new(text).block(notes_block).wrap(Wrap { trim: false }),
         chunks[2],
@@ -365,6 +522,11 @@ fn draw_output(frame: &mut Frame, area: Rect, wizard: &Wizard) {
 
 fn draw_review(frame: &mut Frame, area: Rect, wizard: &Wizard) {
     let s = &wizard.spec;
+    let report = s.physical.imposition_fit();
+    let fit = match report.warning() {
+        Some(warning) if s.output.impose => format!("{report}  WARNING: {warning}"),
+        _ => report.to_string(),
+    };
     let summary = format!(
         "Name:        {}\n\
          Type:        {}\n\
@@ -372,15 +534,20 @@ fn draw_review(frame: &mut Frame, area: Rect, wizard: &Wizard) {
          Stock:       {:.0}\" x {:.0}\"\n\
          Sides:       {}\n\
          Bleed:       {:.3}\"\n\
+         Margins:     {}\n\
+         Grid:        {}\n\
          Quantity:    {}\n\
          Color mode:  {}\n\
          Primary:     {}\n\
          Secondary:   {}\n\
+         Spot colors: {}\n\
          Fonts:       {} / {}\n\
          Domains:     {}\n\
          Refs:        {}\n\
          Formats:     {}\n\
          Impose:      {}\n\
+         Imposition:  {}\n\
+         Previews:    {}\n\
          \n\
          Brief:\n{}\n\
          \n\
@@ -393,22 +560,41 @@ fn draw_review(frame: &mut Frame, area: Rect, wizard: &Wizard) {
         s.physical.stock_height_inches,
         s.physical.sides.label(),
         s.physical.bleed_inches,
+        s.physical.margins,
+        s.grid,
         s.physical.quantity,
         s.color.mode.label(),
         s.color.primary,
         s.color.secondary.as_ref().map_or("(none)".to_string(), |c| format!("{c}")),
+        if s.color.spot_colors.is_empty() {
+            "(none)".to_string()
+        } else {
+            s.color.spot_colors.join(", ")
+        },
         s.typography.primary_font,
         s.typography.secondary_font,
         s.domains.join(", "),
         s.references.len(),
         s.output.formats.join(", "),
         if s.output.impose { "yes" } else { "no" },
+        fit,
+        if s.output.previews { "yes" } else { "no" },
         s.brief,
-        s.scaffold_dir(),
+        wizard
+            .editing
+            .as_deref()
+            .and_then(std::path::Path::parent)
+            .map_or_else(|| s.scaffold_dir(), |dir| dir.display().to_string()),
     );
 
-    let status = if wizard.scaffolded {
+    let status = if wizard.scaffolded && wizard.editing.is_some() {
+        " [SAVED] Press q to exit "
+    } else if wizard.scaffolded {
         " [SCAFFOLDED] Press q to exit "
+    } else if wizard.editing.is_some() {
+        " Press Enter to save changes "
+    } else if wizard.confirm_overwrite {
+        " Directory exists -- press O to overwrite "
     } else {
         " Press Enter to scaffold project "
     };
@@ -1,9 +1,15 @@
-use crate::project::*;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+use grids_intake::project::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Step {
     Name,
     Physical,
+    Grid,
     Color,
     Typography,
     Brief,
@@ -17,6 +23,7 @@ impl Step {
     pub const ALL: &[Step] = &[
         Step::Name,
         Step::Physical,
+        Step::Grid,
         Step::Color,
         Step::Typography,
         Step::Brief,
@@ -34,6 +41,7 @@ impl Step {
         match self {
             Step::Name => "Project Name & Type",
             Step::Physical => "Physical Specs",
+            Step::Grid => "Layout Grid",
             Step::Color => "Color System",
             Step::Typography => "Typography",
             Step::Brief => "Creative Brief",
@@ -44,6 +52,69 @@ impl Step {
         }
     }
 
+    /// The fields this step collects, in tab order.
+    pub fn fields(&self) -> Vec<FieldSchema> {
+        match self {
+            Step::Name => vec![
+                FieldSchema::new("Project Name", FieldKind::Text),
+                FieldSchema::choice("Project Type", ProjectType::VARIANTS),
+            ],
+            Step::Physical => vec![
+                FieldSchema::new("Item Size (inches)", FieldKind::Number),
+                FieldSchema::new("Stock Size (inches)", FieldKind::Number),
+                FieldSchema::choice("Sides", &["single-sided", "double-sided"]),
+                FieldSchema::new("Bleed & Quantity", FieldKind::Number),
+                FieldSchema::new("Margins (inches)", FieldKind::Number),
+            ],
+            Step::Grid => vec![
+                FieldSchema::new("Columns", FieldKind::Number),
+                FieldSchema::new("Rows", FieldKind::Number),
+                FieldSchema::new("Gutter (inches)", FieldKind::Number),
+            ],
+            Step::Color => vec![
+                FieldSchema::choice("Color Mode", &["1-color", "2-color", "full-process (CMYK)"]),
+                FieldSchema::new("Primary Color", FieldKind::Text),
+                FieldSchema::new("Secondary Color", FieldKind::Text),
+                FieldSchema::new("Spot Colors", FieldKind::List),
+            ],
+            Step::Typography => vec![
+                FieldSchema::new("Primary Font", FieldKind::Text),
+                FieldSchema::new("Secondary Font", FieldKind::Text),
+                FieldSchema::new("Typography Notes", FieldKind::Text),
+            ],
+            Step::Brief => vec![FieldSchema::new("Creative Brief", FieldKind::Multiline)],
+            Step::Domains => vec![FieldSchema {
+                label: "Domain Configs",
+                kind: FieldKind::Checklist,
+                options: AVAILABLE_DOMAINS,
+            }],
+            Step::References => vec![FieldSchema::new("Reference Paths", FieldKind::Multiline)],
+            Step::Output => vec![
+                FieldSchema {
+                    label: "Formats",
+                    kind: FieldKind::Checklist,
+                    options: OUTPUT_FORMATS,
+                },
+                FieldSchema::choice("Impose on Stock", &["no", "yes"]),
+                FieldSchema::new("Delivery Notes", FieldKind::Multiline),
+            ],
+            Step::Review => vec![],
+        }
+    }
+
+    pub fn schema(&self) -> StepSchema {
+        StepSchema {
+            step: *self,
+            title: self.title().to_string(),
+            fields: self.fields(),
+        }
+    }
+
+    /// Schemas for every step, so other frontends can render the same wizard.
+    pub fn schema_all() -> Vec<StepSchema> {
+        Step::ALL.iter().map(Step::schema).collect()
+    }
+
     pub fn next(&self) -> Option<Step> {
         let i = self.index();
         Step::ALL.get(i + 1).copied()
@@ -59,18 +130,92 @@ impl Step {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldKind {
+    Text,
+    Number,
+    Choice,
+    Multiline,
+    Checklist,
+    /// Free-text entries added one at a time.
+    List,
+}
+
+/// Describes one input of a wizard step, independent of the TUI.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldSchema {
+    pub label: &'static str,
+    pub kind: FieldKind,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub options: &'static [&'static str],
+}
+
+impl FieldSchema {
+    const fn new(label: &'static str, kind: FieldKind) -> Self {
+        Self { label, kind, options: &[] }
+    }
+
+    const fn choice(label: &'static str, options: &'static [&'static str]) -> Self {
+        Self { label, kind: FieldKind::Choice, options }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StepSchema {
+    pub step: Step,
+    pub title: String,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// The wizard's state. It serializes whole, pending input included, so a
+/// draft resumes exactly where it was left; only transient prompts are
+/// skipped.
+#[derive(Serialize, Deserialize)]
 pub struct Wizard {
     pub step: Step,
     pub spec: ProjectSpec,
     pub input_buf: String,
+    /// Byte offset of the cursor in `input_buf`, always on a char boundary.
+    /// Only the multiline fields move it; it rests at the end otherwise.
+    #[serde(default)]
+    pub cursor: usize,
     pub field_index: usize,
     pub type_index: usize,
     pub sides_index: usize,
     pub color_mode_index: usize,
+    /// The selected entry in the spot color list.
+    pub spot_index: usize,
+    /// Domains offered in the Domains step: the built-in ones, then any the
+    /// spec or the user added. `domain_toggles` runs parallel to it.
+    pub domain_options: Vec<String>,
     pub domain_toggles: Vec<bool>,
+    /// One per [`OUTPUT_FORMATS`] entry, and the one under the cursor.
+    pub format_toggles: Vec<bool>,
+    pub format_index: usize,
     #[allow(dead_code)]
     pub confirmed: bool,
     pub scaffolded: bool,
+    /// Why the last attempt to advance was refused, shown until fixed.
+    #[serde(skip)]
+    pub field_error: Option<String>,
+    /// What the last action did, such as saving a draft; shown until the
+    /// next key.
+    #[serde(skip)]
+    pub notice: Option<String>,
+    /// The `project.yaml` being edited, when started with `--edit`. Saving
+    /// rewrites it in place rather than scaffolding a new project.
+    pub editing: Option<PathBuf>,
+    /// Scaffolding stopped because the project directory exists; the Review
+    /// step asks before overwriting it.
+    #[serde(skip)]
+    pub confirm_overwrite: bool,
+    /// The spec has changes that haven't been scaffolded or saved.
+    pub dirty: bool,
+    /// A quit was refused because of unsaved changes; a second quit goes
+    /// through.
+    #[serde(skip)]
+    pub confirm_quit: bool,
 }
 
 const AVAILABLE_DOMAINS: &[&str] = &[
@@ -81,23 +226,253 @@ const AVAILABLE_DOMAINS: &[&str] = &[
     "agency-mix",
 ];
 
+/// Output formats the Output step can toggle.
+const OUTPUT_FORMATS: &[&str] = &["pdf", "svg", "png", "html", "idml", "latex"];
+
+/// Parse "top right bottom left" margins in inches; a single value applies to
+/// all four edges.
+fn parse_margins(input: &str) -> Option<MarginSpec> {
+    let values: Vec<f64> = input
+        .split_whitespace()
+        .map(|v| v.parse().ok().filter(|v: &f64| *v >= 0.0))
+        .collect::<Option<_>>()?;
+    match values.as_slice() {
+        [all] => Some(MarginSpec {
+            top: *all,
+            right: *all,
+            bottom: *all,
+            left: *all,
+        }),
+        [top, right, bottom, left] => Some(MarginSpec {
+            top: *top,
+            right: *right,
+            bottom: *bottom,
+            left: *left,
+        }),
+        _ => None,
+    }
+}
+
+/// Parse "width x height" in inches; the `x` is optional.
+fn parse_size(input: &str) -> Option<(f64, f64)> {
+    let values: Vec<f64> = input
+        .split(|c: char| c.is_whitespace() || c == 'x')
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().ok())
+        .collect::<Option<_>>()?;
+    match values.as_slice() {
+        [w, h] => Some((*w, *h)),
+        _ => None,
+    }
+}
+
+/// Parse `input` into physical field `field` (item size, stock size, bleed
+/// and quantity, margins), leaving `physical` untouched when it doesn't
+/// parse. The sides toggle isn't text and always succeeds.
+fn apply_physical_field(
+    physical: &mut PhysicalSpec,
+    field: usize,
+    input: &str,
+) -> Result<(), String> {
+    let size = |what: &str| {
+        parse_size(input).ok_or_else(|| format!("{what} must be width x height in inches"))
+    };
+    match field {
+        0 => (physical.item_width_inches, physical.item_height_inches) = size("Item size")?,
+        1 => (physical.stock_width_inches, physical.stock_height_inches) = size("Stock size")?,
+        3 => {
+            let bad = || "Bleed & quantity must be bleed inches then a whole quantity".to_string();
+            let mut parts = input.split_whitespace();
+            let (Some(bleed), Some(quantity), None) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(bad());
+            };
+            let bleed = bleed.parse().map_err(|_| bad())?;
+            physical.quantity = quantity.parse().map_err(|_| bad())?;
+            physical.bleed_inches = bleed;
+        }
+        4 => {
+            physical.margins = parse_margins(input).ok_or_else(|| {
+                "Margins must be one value or four (top right bottom left)".to_string()
+            })?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Parse "name c m y k", the four components being percentages 0-100. The
+/// name may contain spaces; it is everything before the last four values.
+fn parse_cmyk(input: &str) -> Result<CmykColor, String> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let bad = || "Color must be a name then C M Y K percentages, e.g. Red 0 100 100 0".to_string();
+    if words.len() < 5 {
+        return Err(bad());
+    }
+    let (name, values) = words.split_at(words.len() - 4);
+    let values: Vec<f64> = values
+        .iter()
+        .map(|v| v.parse().map_err(|_| bad()))
+        .collect::<Result<_, _>>()?;
+    if values.iter().any(|v| !(0.0..=100.0).contains(v)) {
+        return Err("C, M, Y and K must each be between 0 and 100".to_string());
+    }
+    Ok(CmykColor {
+        c: values[0],
+        m: values[1],
+        y: values[2],
+        k: values[3],
+        name: name.join(" "),
+    })
+}
+
+/// The editable text form of `color`, as read by [`parse_cmyk`].
+fn cmyk_input(color: &CmykColor) -> String {
+    format!("{} {} {} {} {}", color.name, color.c, color.m, color.y, color.k)
+}
+
+/// Parse `input` into color field `field` (primary, secondary, spot colors).
+/// An empty secondary clears it, and a 1-color job can't have one; spot
+/// color input is added to the list unless it's blank or already there.
+fn apply_color_field(
+    color: &mut ColorSpec,
+    mode_index: usize,
+    field: usize,
+    input: &str,
+) -> Result<(), String> {
+    match field {
+        1 => color.primary = parse_cmyk(input)?,
+        2 if input.trim().is_empty() => color.secondary = None,
+        2 if mode_index == 0 => {
+            return Err("A 1-color job has no secondary color".to_string());
+        }
+        2 => color.secondary = Some(parse_cmyk(input)?),
+        3 => {
+            let spot = input.trim();
+            if !spot.is_empty() && !color.spot_colors.iter().any(|s| s == spot) {
+                color.spot_colors.push(spot.to_string());
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Parse `input` into grid field `field` (columns, rows, gutter), leaving
+/// `grid` untouched when it doesn't parse.
+fn apply_grid_field(grid: &mut GridSpec, field: usize, input: &str) -> Result<(), String> {
+    let input = input.trim();
+    match field {
+        0 => grid.columns = input.parse().map_err(|_| "Columns must be a whole number")?,
+        1 => grid.rows = input.parse().map_err(|_| "Rows must be a whole number")?,
+        _ => grid.gutter_inches = input.parse().map_err(|_| "Gutter must be a number of inches")?,
+    }
+    Ok(())
+}
+
+/// Position of `sides` in the Physical step's Sides choice.
+fn sides_index(sides: &Sides) -> usize {
+    match sides {
+        Sides::Single => 0,
+        Sides::Double => 1,
+    }
+}
+
+/// Position of `mode` in the Color step's Color Mode choice.
+fn color_mode_index(mode: &ColorMode) -> usize {
+    match mode {
+        ColorMode::OneColor => 0,
+        ColorMode::TwoColor => 1,
+        ColorMode::FullProcess => 2,
+    }
+}
+
+/// Domains selected for a new project when no selection has been saved.
+const DEFAULT_DOMAINS: &[&str] = &["design", "creative-production"];
+
 impl Wizard {
+    /// A wizard for a new project, with the last-used domain selection.
     pub fn new() -> Self {
+        let domains = load_default_domains()
+            .unwrap_or_else(|| DEFAULT_DOMAINS.iter().map(|d| d.to_string()).collect());
+        Self::from_spec(ProjectSpec {
+            domains,
+            ..ProjectSpec::default()
+        })
+    }
+
+    /// A wizard pre-filled from an existing spec; choice fields and domain
+    /// toggles are derived from it.
+    pub fn from_spec(spec: ProjectSpec) -> Self {
+        let type_index = ProjectType::VARIANTS
+            .iter()
+            .position(|v| *v == spec.project_type.label())
+            .unwrap_or(ProjectType::VARIANTS.len() - 1);
+        let sides_index = sides_index(&spec.physical.sides);
+        let color_mode_index = color_mode_index(&spec.color.mode);
+        let mut domain_options: Vec<String> =
+            AVAILABLE_DOMAINS.iter().map(|d| d.to_string()).collect();
+        for d in &spec.domains {
+            if !domain_options.contains(d) {
+                domain_options.push(d.clone());
+            }
+        }
+        let domain_toggles = domain_options
+            .iter()
+            .map(|d| spec.domains.contains(d))
+            .collect();
+        let format_toggles = OUTPUT_FORMATS
+            .iter()
+            .map(|f| spec.output.formats.iter().any(|s| s == f))
+            .collect();
         Self {
             step: Step::Name,
-            spec: ProjectSpec::default(),
+            spec,
             input_buf: String::new(),
+            cursor: 0,
             field_index: 0,
-            type_index: 0,
-            sides_index: 1,
-            color_mode_index: 1,
-            domain_toggles: vec![true, false, false, true, false],
+            type_index,
+            sides_index,
+            color_mode_index,
+            spot_index: 0,
+            domain_options,
+            domain_toggles,
+            format_toggles,
+            format_index: 0,
             confirmed: false,
             scaffolded: false,
+            field_error: None,
+            notice: None,
+            editing: None,
+            confirm_overwrite: false,
+            dirty: false,
+            confirm_quit: false,
         }
     }
 
+    /// A wizard editing the spec at `path`, opened on the Review step. Keys
+    /// the spec doesn't recognize are listed in `field_error`.
+    pub fn from_yaml(path: &Path) -> std::io::Result<Self> {
+        let yaml = std::fs::read_to_string(path)?;
+        let (spec, warnings) =
+            ProjectSpec::from_yaml_lenient(&yaml).map_err(std::io::Error::other)?;
+        let mut wizard = Self::from_spec(spec);
+        wizard.step = Step::Review;
+        wizard.editing = Some(path.to_path_buf());
+        if !warnings.is_empty() {
+            wizard.field_error = Some(format!("Ignoring unknown keys: {}", warnings.join(", ")));
+        }
+        Ok(wizard)
+    }
+
+    /// Move to the next step, unless the current step's input is invalid, in
+    /// which case nothing is committed and `field_error` explains why.
     pub fn advance(&mut self) {
+        if let Err(e) = self.validate_current() {
+            self.field_error = Some(e);
+            return;
+        }
+        self.field_error = None;
         self.commit_current();
         if let Some(next) = self.step.next() {
             self.step = next;
@@ -106,7 +481,15 @@ impl Wizard {
         }
     }
 
+    /// Move to the previous step. This always works: input in the focused
+    /// field that doesn't parse is discarded, and `notice` says so.
     pub fn go_back(&mut self) {
+        self.confirm_overwrite = false;
+        self.field_error = None;
+        if let Some(e) = self.field_parse_error() {
+            self.notice = Some(format!("Discarded unparseable input: {e}"));
+            self.load_step_buf();
+        }
         self.commit_current();
         if let Some(prev) = self.step.prev() {
             self.step = prev;
@@ -119,22 +502,158 @@ impl Wizard {
         self.input_buf = match self.step {
             Step::Name => self.spec.name.clone(),
             Step::Brief => self.spec.brief.clone(),
+            Step::Physical => {
+                let p = &self.spec.physical;
+                match self.field_index {
+                    0 => format!("{} x {}", p.item_width_inches, p.item_height_inches),
+                    1 => format!("{} x {}", p.stock_width_inches, p.stock_height_inches),
+                    3 => format!("{} {}", p.bleed_inches, p.quantity),
+                    4 => {
+                        let m = &p.margins;
+                        format!("{} {} {} {}", m.top, m.right, m.bottom, m.left)
+                    }
+                    _ => String::new(),
+                }
+            }
+            Step::Grid => match self.field_index {
+                0 => self.spec.grid.columns.to_string(),
+                1 => self.spec.grid.rows.to_string(),
+                _ => self.spec.grid.gutter_inches.to_string(),
+            },
+            Step::Color => match self.field_index {
+                1 => cmyk_input(&self.spec.color.primary),
+                2 => self.spec.color.secondary.as_ref().map(cmyk_input).unwrap_or_default(),
+                _ => String::new(),
+            },
             Step::Typography => match self.field_index {
                 0 => self.spec.typography.primary_font.clone(),
                 1 => self.spec.typography.secondary_font.clone(),
                 _ => self.spec.typography.notes.clone(),
             },
             Step::References => self.spec.references.join("\n"),
-            Step::Output => self.spec.output.delivery_notes.clone(),
+            Step::Output if self.field_index == 2 => self.spec.output.delivery_notes.clone(),
             _ => String::new(),
         };
+        self.cursor = self.input_buf.len();
+    }
+
+    /// Insert `text` at the cursor and move past it.
+    pub fn insert_str(&mut self, text: &str) {
+        self.cursor = self.cursor.min(self.input_buf.len());
+        self.input_buf.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Delete the character before the cursor (Backspace).
+    pub fn delete_back(&mut self) {
+        if let Some((i, _)) = self.input_buf[..self.cursor].char_indices().next_back() {
+            self.input_buf.remove(i);
+            self.cursor = i;
+        }
+    }
+
+    /// Delete the character under the cursor (Delete).
+    pub fn delete_forward(&mut self) {
+        if self.cursor < self.input_buf.len() {
+            self.input_buf.remove(self.cursor);
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some((i, _)) = self.input_buf[..self.cursor].char_indices().next_back() {
+            self.cursor = i;
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(c) = self.input_buf[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    /// Move to the start of the cursor's line.
+    pub fn cursor_home(&mut self) {
+        self.cursor = self.input_buf[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+    }
+
+    /// Move to the end of the cursor's line.
+    pub fn cursor_end(&mut self) {
+        self.cursor = self.input_buf[self.cursor..]
+            .find('\n')
+            .map_or(self.input_buf.len(), |i| self.cursor + i);
+    }
+
+    /// Check the current step's pending input without committing it.
+    pub fn validate_current(&self) -> Result<(), String> {
+        match self.step {
+            Step::Name if self.input_buf.trim().is_empty() => {
+                Err("Project name is required".to_string())
+            }
+            Step::Physical => {
+                let mut candidate = self.spec.clone();
+                apply_physical_field(&mut candidate.physical, self.field_index, &self.input_buf)?;
+                match candidate.validate().into_iter().next() {
+                    Some(problem) => Err(problem),
+                    None => Ok(()),
+                }
+            }
+            Step::Color => {
+                let mut color = self.spec.color.clone();
+                let mode = self.color_mode_index;
+                apply_color_field(&mut color, mode, self.field_index, &self.input_buf)?;
+                if mode == 0 && color.secondary.is_some() {
+                    return Err("A 1-color job has no secondary color; clear it with Del".into());
+                }
+                Ok(())
+            }
+            Step::Grid => {
+                let mut candidate = self.spec.clone();
+                apply_grid_field(&mut candidate.grid, self.field_index, &self.input_buf)?;
+                match candidate.validate().into_iter().next() {
+                    Some(problem) => Err(problem),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
     }
 
+    /// Write the current step's input into the spec, marking the wizard
+    /// dirty if that changed anything.
     pub fn commit_current(&mut self) {
+        let before = self.spec.clone();
+        self.commit_step();
+        if self.spec != before {
+            self.dirty = true;
+        }
+    }
+
+    fn commit_step(&mut self) {
         match self.step {
             Step::Name => {
                 self.spec.name = self.input_buf.trim().to_string();
-                self.spec.project_type = ProjectType::from_index(self.type_index, &self.input_buf);
+                let project_type = ProjectType::from_index(self.type_index, &self.input_buf);
+                if project_type.label() != self.spec.project_type.label() {
+                    // Presets replace only what still matches the old type's
+                    // preset, so hand-entered specs survive a type change.
+                    let old_type = &self.spec.project_type;
+                    if self.spec.grid == GridSpec::for_type(old_type) {
+                        self.spec.grid = GridSpec::for_type(&project_type);
+                    }
+                    if self.spec.physical == PhysicalSpec::for_type(old_type) {
+                        self.spec.physical = PhysicalSpec::for_type(&project_type);
+                    }
+                    if self.spec.color == ColorSpec::for_type(old_type) {
+                        self.spec.color = ColorSpec::for_type(&project_type);
+                    }
+                    self.sides_index = sides_index(&self.spec.physical.sides);
+                    self.color_mode_index = color_mode_index(&self.spec.color.mode);
+                }
+                self.spec.project_type = project_type;
             }
             Step::Physical => {
                 self.spec.physical.sides = if self.sides_index == 0 {
@@ -142,6 +661,14 @@ impl Wizard {
                 } else {
                     Sides::Double
                 };
+                let _ = apply_physical_field(
+                    &mut self.spec.physical,
+                    self.field_index,
+                    &self.input_buf,
+                );
+            }
+            Step::Grid => {
+                let _ = apply_grid_field(&mut self.spec.grid, self.field_index, &self.input_buf);
             }
             Step::Color => {
                 self.spec.color.mode = match self.color_mode_index {
@@ -149,6 +676,12 @@ impl Wizard {
                     1 => ColorMode::TwoColor,
                     _ => ColorMode::FullProcess,
                 };
+                let _ = apply_color_field(
+                    &mut self.spec.color,
+                    self.color_mode_index,
+                    self.field_index,
+                    &self.input_buf,
+                );
             }
             Step::Typography => match self.field_index {
                 0 => self.spec.typography.primary_font = self.input_buf.trim().to_string(),
@@ -159,11 +692,12 @@ impl Wizard {
                 self.spec.brief = self.input_buf.trim().to_string();
             }
             Step::Domains => {
-                self.spec.domains = AVAILABLE_DOMAINS
+                self.spec.domains = self
+                    .domain_options
                     .iter()
                     .zip(self.domain_toggles.iter())
                     .filter(|(_, &on)| on)
-                    .map(|(d, _)| d.to_string())
+                    .map(|(d, _)| d.clone())
                     .collect();
             }
             Step::References => {
@@ -175,34 +709,178 @@ impl Wizard {
                     .collect();
             }
             Step::Output => {
-                self.spec.output.delivery_notes = self.input_buf.trim().to_string();
+                // Formats outside the known set (from a hand-edited spec) are kept.
+                let mut formats: Vec<String> = OUTPUT_FORMATS
+                    .iter()
+                    .zip(self.format_toggles.iter())
+                    .filter(|(_, &on)| on)
+                    .map(|(f, _)| f.to_string())
+                    .collect();
+                formats.extend(
+                    self.spec
+                        .output
+                        .formats
+                        .iter()
+                        .filter(|f| !OUTPUT_FORMATS.contains(&f.as_str()))
+                        .cloned(),
+                );
+                self.spec.output.formats = formats;
+                if self.field_index == 2 {
+                    self.spec.output.delivery_notes = self.input_buf.trim().to_string();
+                }
             }
             Step::Review => {}
         }
     }
 
+    /// Add a default secondary color, or clear the existing one. Only 2-color
+    /// and full-process jobs can add one.
+    pub fn toggle_secondary(&mut self) {
+        self.spec.color.secondary = match self.spec.color.secondary {
+            Some(_) => None,
+            None if self.color_mode_index == 0 => {
+                self.field_error = Some("A 1-color job has no secondary color".to_string());
+                return;
+            }
+            None => Some(CmykColor {
+                c: 0.0,
+                m: 100.0,
+                y: 100.0,
+                k: 0.0,
+                name: "Red".to_string(),
+            }),
+        };
+        self.dirty = true;
+        self.load_step_buf();
+    }
+
+    /// Add the typed spot color to the list and select it.
+    pub fn add_spot_color(&mut self) {
+        self.commit_current();
+        let spot = self.input_buf.trim();
+        if let Some(i) = self.spec.color.spot_colors.iter().position(|s| s == spot) {
+            self.spot_index = i;
+        }
+        self.input_buf.clear();
+    }
+
+    /// Remove the selected spot color.
+    pub fn remove_spot_color(&mut self) {
+        let spots = &mut self.spec.color.spot_colors;
+        if self.spot_index < spots.len() {
+            spots.remove(self.spot_index);
+            self.dirty = true;
+            self.spot_index = self.spot_index.min(spots.len().saturating_sub(1));
+        }
+    }
+
+    /// Replace the last line of the references input, read as a directory,
+    /// with the reference files inside it.
+    pub fn scan_reference_dir(&mut self, recursive: bool) {
+        let last_line = self.input_buf.lines().last().unwrap_or("");
+        let dir = last_line.trim().to_string();
+        if dir.is_empty() {
+            self.field_error = Some("Type a directory on the last line first".to_string());
+            return;
+        }
+        match scan_references(std::path::Path::new(&dir), recursive) {
+            Ok(files) => {
+                let mut lines: Vec<String> = self.input_buf.lines().map(String::from).collect();
+                lines.pop();
+                lines.extend(files.iter().map(|f| f.display().to_string()));
+                self.input_buf = lines.join("\n");
+                self.cursor = self.input_buf.len();
+                self.field_error = None;
+            }
+            Err(e) => self.field_error = Some(format!("Can't scan {dir}: {e}")),
+        }
+    }
+
     pub fn toggle_domain(&mut self) {
         if self.field_index < self.domain_toggles.len() {
             self.domain_toggles[self.field_index] = !self.domain_toggles[self.field_index];
         }
     }
 
-    pub fn available_domains(&self) -> &[&str] {
-        AVAILABLE_DOMAINS
+    /// Check or uncheck the output format under the cursor.
+    pub fn toggle_format(&mut self) {
+        if let Some(on) = self.format_toggles.get_mut(self.format_index) {
+            *on = !*on;
+        }
+    }
+
+    pub fn output_formats(&self) -> &[&str] {
+        OUTPUT_FORMATS
+    }
+
+    pub fn available_domains(&self) -> &[String] {
+        &self.domain_options
+    }
+
+    /// Whether the Domains step's focus is on the new-domain input, which
+    /// follows the list.
+    pub fn on_new_domain(&self) -> bool {
+        self.step == Step::Domains && self.field_index == self.domain_options.len()
+    }
+
+    /// Add the typed domain to the list, checked. Typing one already listed
+    /// just checks it.
+    pub fn add_domain(&mut self) {
+        let name = self.input_buf.trim().to_string();
+        self.input_buf.clear();
+        if name.is_empty() {
+            return;
+        }
+        match self.domain_options.iter().position(|d| *d == name) {
+            Some(i) => self.domain_toggles[i] = true,
+            None => {
+                self.domain_options.push(name);
+                self.domain_toggles.push(true);
+            }
+        }
+        self.field_index = self.domain_options.len();
     }
 
     pub fn field_count(&self) -> usize {
         match self.step {
             Step::Name => 2,
-            Step::Physical => 4,
-            Step::Color => 3,
+            Step::Physical => 5,
+            Step::Grid => 3,
+            Step::Color => 4,
             Step::Typography => 3,
-            Step::Domains => AVAILABLE_DOMAINS.len(),
+            Step::Output => 3,
+            Step::Domains => self.domain_options.len() + 1,
             _ => 1,
         }
     }
 
+    /// Why the focused numeric field's input won't parse, if it doesn't.
+    /// Moving between fields is refused until it does, so a typo isn't
+    /// silently dropped.
+    fn field_parse_error(&self) -> Option<String> {
+        match self.step {
+            Step::Physical => {
+                let mut physical = self.spec.physical.clone();
+                apply_physical_field(&mut physical, self.field_index, &self.input_buf).err()
+            }
+            Step::Grid => {
+                let mut grid = self.spec.grid.clone();
+                apply_grid_field(&mut grid, self.field_index, &self.input_buf).err()
+            }
+            Step::Color => {
+                let mut color = self.spec.color.clone();
+                let mode = self.color_mode_index;
+                apply_color_field(&mut color, mode, self.field_index, &self.input_buf).err()
+            }
+            _ => None,
+        }
+    }
+
     pub fn next_field(&mut self) {
+        self.field_error = self.field_parse_error();
+        if self.field_error.is_some() {
+            return;
+        }
         self.commit_current();
         if self.field_index + 1 < self.field_count() {
             self.field_index += 1;
@@ -211,6 +889,10 @@ impl Wizard {
     }
 
     pub fn prev_field(&mut self) {
+        self.field_error = self.field_parse_error();
+        if self.field_error.is_some() {
+            return;
+        }
         self.commit_current();
         if self.field_index > 0 {
             self.field_index -= 1;
@@ -218,25 +900,198 @@ impl Wizard {
         }
     }
 
-    pub fn scaffold(&self) -> std::io::Result<String> {
-        let dir = self.spec.scaffold_dir();
-        std::fs::create_dir_all(format!("{dir}/cards/front"))?;
-        std::fs::create_dir_all(format!("{dir}/cards/back"))?;
-        std::fs::create_dir_all(format!("{dir}/reference"))?;
-        std::fs::create_dir_all(format!("{dir}/moodboard"))?;
-        std::fs::create_dir_all(format!("{dir}/output"))?;
+    /// Where Ctrl-S saves this wizard's draft: named for the project, under
+    /// `~/.grids/drafts/`.
+    pub fn draft_path(&self) -> Option<PathBuf> {
+        let slug = self.spec.name_slug();
+        let name = if slug.is_empty() { "untitled" } else { slug.as_str() };
+        Some(drafts_dir()?.join(format!("{name}.json")))
+    }
 
-        let yaml = self.spec.to_yaml().map_err(|e| {
-            std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
-        })?;
-        std::fs::write(format!("{dir}/project.yaml"), &yaml)?;
+    /// Write the whole wizard state to `path` as JSON.
+    pub fn save_draft(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
 
-        let brief = self.spec.brief_md();
-        std::fs::write(format!("{dir}/brief.md"), &brief)?;
+    /// Resume a wizard saved by [`save_draft`](Self::save_draft), on the
+    /// same step and field with the same pending input.
+    /// A stale or hand-edited cursor is pulled back inside the input, onto
+    /// a character boundary.
+    pub fn load_draft(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let mut wizard: Self = serde_json::from_str(&json).map_err(std::io::Error::other)?;
+        let mut cursor = wizard.cursor.min(wizard.input_buf.len());
+        while !wizard.input_buf.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        wizard.cursor = cursor;
+        Ok(wizard)
+    }
 
-        std::fs::write(format!("{dir}/decisions.json"), "[]")?;
-        std::fs::write(format!("{dir}/design-notes.md"), &format!("# Design Notes: {}\n", self.spec.name))?;
+    /// Write the edited spec back over the `project.yaml` it was loaded from,
+    /// refreshing `brief.md` beside it. Returns the project directory.
+    pub fn save_edits(&self) -> std::io::Result<String> {
+        let path = self
+            .editing
+            .as_deref()
+            .ok_or_else(|| std::io::Error::other("not editing an existing project"))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        std::fs::write(path, self.spec.to_yaml().map_err(std::io::Error::other)?)?;
+        std::fs::write(dir.join("brief.md"), self.spec.brief_md())?;
+        Ok(dir.display().to_string())
+    }
 
-        Ok(dir)
+    /// Scaffold the project directory; see [`scaffold_spec_with`]. Without
+    /// progress reporting, call [`scaffold_spec`] on `spec` directly.
+    pub fn scaffold_with_progress<F: FnMut(ScaffoldEvent)>(
+        &self,
+        overwrite: bool,
+        progress: F,
+    ) -> std::io::Result<String> {
+        scaffold_spec_with(&self.spec, overwrite, progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wizard_on(step: Step) -> Wizard {
+        let mut wizard = Wizard::from_spec(ProjectSpec::default());
+        wizard.step = step;
+        wizard.load_step_buf();
+        wizard
+    }
+
+    #[test]
+    fn physical_schema_lists_its_fields() {
+        let schema = Step::Physical.schema();
+        let fields: Vec<(&str, FieldKind)> =
+            schema.fields.iter().map(|f| (f.label, f.kind)).collect();
+        assert_eq!(
+            fields,
+            [
+                ("Item Size (inches)", FieldKind::Number),
+                ("Stock Size (inches)", FieldKind::Number),
+                ("Sides", FieldKind::Choice),
+                ("Bleed & Quantity", FieldKind::Number),
+                ("Margins (inches)", FieldKind::Number),
+            ]
+        );
+        assert_eq!(schema.fields[2].options, ["single-sided", "double-sided"]);
+
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["step"], "physical");
+        assert_eq!(json["fields"][2]["kind"], "choice");
+    }
+
+    #[test]
+    fn invalid_physical_dimension_blocks_advance() {
+        for input in ["abc", "0 x 2", "3"] {
+            let mut wizard = wizard_on(Step::Physical);
+            wizard.input_buf = input.to_string();
+            wizard.advance();
+            assert_eq!(wizard.step, Step::Physical, "{input}");
+            assert!(wizard.field_error.is_some(), "{input}");
+        }
+    }
+
+    #[test]
+    fn valid_physical_dimension_advances() {
+        let mut wizard = wizard_on(Step::Physical);
+        wizard.input_buf = "3.5 x 2".to_string();
+        wizard.advance();
+        assert_eq!(Some(wizard.step), Step::Physical.next());
+        assert_eq!(wizard.field_error, None);
+        assert_eq!(wizard.spec.physical.item_width_inches, 3.5);
+        assert_eq!(wizard.spec.physical.item_height_inches, 2.0);
+    }
+
+    #[test]
+    fn type_change_keeps_edited_specs() {
+        let mut wizard = wizard_on(Step::Name);
+        wizard.input_buf = "Show poster".to_string();
+        wizard.spec.grid.columns += 1;
+        let edited_grid = wizard.spec.grid.clone();
+        wizard.type_index = 2;
+        wizard.advance();
+        assert_eq!(wizard.spec.project_type, ProjectType::Poster);
+        assert_eq!(wizard.spec.grid, edited_grid);
+        assert_eq!(wizard.spec.physical, PhysicalSpec::for_type(&ProjectType::Poster));
+    }
+
+    #[test]
+    fn go_back_discards_unparseable_input() {
+        let mut wizard = wizard_on(Step::Grid);
+        let columns = wizard.spec.grid.columns;
+        wizard.input_buf = "3.5.1".to_string();
+        wizard.go_back();
+        assert_eq!(Some(wizard.step), Step::Grid.prev());
+        assert_eq!(wizard.spec.grid.columns, columns);
+        assert_eq!(wizard.field_error, None);
+        assert!(wizard.notice.is_some());
+
+        let mut wizard = wizard_on(Step::Grid);
+        wizard.input_buf = "4".to_string();
+        wizard.go_back();
+        assert_eq!(Some(wizard.step), Step::Grid.prev());
+        assert_eq!(wizard.spec.grid.columns, 4);
+    }
+
+    #[test]
+    fn load_draft_clamps_the_cursor() {
+        let dir = std::env::temp_dir().join(format!("grids-draft-{}", std::process::id()));
+        let path = dir.join("draft.json");
+        for (cursor, expected) in [(99, 3), (2, 1)] {
+            let mut wizard = wizard_on(Step::Brief);
+            wizard.input_buf = "né".to_string();
+            wizard.cursor = cursor;
+            wizard.save_draft(&path).unwrap();
+            let mut loaded = Wizard::load_draft(&path).unwrap();
+            assert_eq!(loaded.cursor, expected);
+            loaded.delete_back();
+            loaded.cursor_right();
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn toggle_secondary_round_trips() {
+        let mut wizard = wizard_on(Step::Color);
+        wizard.color_mode_index = 1;
+        wizard.spec.color.secondary = None;
+
+        wizard.toggle_secondary();
+        assert_eq!(wizard.spec.color.secondary.as_ref().map(|c| c.name.as_str()), Some("Red"));
+        assert!(wizard.dirty);
+        wizard.toggle_secondary();
+        assert!(wizard.spec.color.secondary.is_none());
+
+        wizard.color_mode_index = 0;
+        wizard.toggle_secondary();
+        assert!(wizard.spec.color.secondary.is_none());
+        assert!(wizard.field_error.is_some());
+    }
+
+    #[test]
+    fn loaded_spec_sets_exactly_its_domain_toggles() {
+        let spec = ProjectSpec {
+            domains: vec!["editorial".to_string()],
+            ..ProjectSpec::default()
+        };
+        let wizard = Wizard::from_spec(spec);
+        let on: Vec<&str> = wizard
+            .domain_options
+            .iter()
+            .zip(&wizard.domain_toggles)
+            .filter(|(_, on)| **on)
+            .map(|(d, _)| d.as_str())
+            .collect();
+        assert_eq!(on, ["editorial"]);
+        assert_eq!(wizard.domain_options.len(), AVAILABLE_DOMAINS.len());
     }
 }
@@ -0,0 +1,54 @@
+//! Runs the `grids-render` binary against fixture projects.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use grids_intake::project::ProjectSpec;
+
+/// A fresh project directory holding just `spec` as its `project.yaml`.
+fn fixture(name: &str, spec: &ProjectSpec) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("grids-render-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("project.yaml"), spec.to_yaml().unwrap()).unwrap();
+    dir
+}
+
+fn render(dir: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_grids-render"))
+        .arg(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn renders_each_listed_format_into_output() {
+    let mut spec = ProjectSpec::default();
+    spec.output.formats = ["svg", "latex", "html"].map(String::from).to_vec();
+    let dir = fixture("ok", &spec);
+
+    let out = render(&dir);
+    let svg = std::fs::read_to_string(dir.join("output/page-1.svg"));
+    let tex = dir.join("output/page-1.tex").is_file();
+    let html = dir.join("output/page-1.html").is_file();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert!(svg.unwrap().starts_with("<svg"));
+    assert!(tex && html);
+}
+
+#[test]
+fn invalid_spec_exits_non_zero() {
+    let mut spec = ProjectSpec::default();
+    spec.grid.columns = 0;
+    let dir = fixture("invalid", &spec);
+
+    let out = render(&dir);
+    let wrote_anything = dir.join("output").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid spec"));
+    assert!(!wrote_anything);
+}
@@ -1,16 +1,144 @@
+mod preview;
+
 use std::io;
+use std::path::{Path, PathBuf};
 
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyEvent},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
+use grids_intake::project::{load_pages, ProjectSpec};
+use grids_layout::page::Page;
+use grids_layout::preflight::PreflightOptions;
+use grids_layout::units::Unit;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Paragraph},
 };
 
+/// What the viewer is showing and how.
+struct Viewer {
+    page: Option<Page>,
+    unit: Unit,
+}
+
+impl Viewer {
+    /// Metrics for the loaded page, every measurement in the current unit.
+    fn lines(&self) -> Vec<String> {
+        let Some(page) = &self.page else {
+            return vec![
+                "No project loaded: run `grids-viewer <project-dir>`.".to_string(),
+                String::new(),
+                "Press 'q' to quit.".to_string(),
+            ];
+        };
+        let fmt = |pt: f64| self.unit.format(pt);
+        let (w, h) = page.size.dimensions();
+        let g = &page.grid;
+        let m = &g.margin;
+        let mut lines = vec![
+            format!("Page {}: {} x {}", page.number, fmt(w), fmt(h)),
+            format!(
+                "Grid: {} x {}, cell {} x {}",
+                g.columns,
+                g.rows,
+                fmt(g.column_width),
+                fmt(g.row_height)
+            ),
+            format!("Gutters: {} / {}", fmt(g.gutter_h), fmt(g.gutter_v)),
+            format!(
+                "Margins: {} / {} / {} / {}",
+                fmt(m.top),
+                fmt(m.right),
+                fmt(m.bottom),
+                fmt(m.left)
+            ),
+            String::new(),
+        ];
+        for b in &page.blocks {
+            let r = g.cell_rect(b.col, b.row, b.col_span, b.row_span);
+            lines.push(format!("{}: {} x {}", b.id, fmt(r.w), fmt(r.h)));
+        }
+        lines.push(String::new());
+        let report = page.preflight(&PreflightOptions::default());
+        let verdict = if report.passed() { "pass" } else { "FAIL" };
+        lines.push(format!(
+            "Preflight: {verdict} ({} errors, {} warnings)",
+            report.errors().count(),
+            report.warnings().count()
+        ));
+        for issue in &report.issues {
+            lines.push(issue.to_string());
+        }
+        lines.push(String::new());
+        lines.push(format!(
+            "Units: {} -- press 'u' to cycle, 'q' to quit.",
+            self.unit.suffix()
+        ));
+        lines
+    }
+}
+
+/// The first page of the project in `dir`.
+fn load_first_page(dir: &Path) -> Result<Page, String> {
+    let yaml = std::fs::read_to_string(dir.join("project.yaml"))
+        .map_err(|e| format!("reading project.yaml: {e}"))?;
+    let (spec, _) =
+        ProjectSpec::from_yaml_lenient(&yaml).map_err(|e| format!("parsing project.yaml: {e}"))?;
+    load_pages(dir, &spec)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "project has no pages".to_string())
+}
+
+/// The page drawn as characters, centered in `area`.
+fn draw_preview(frame: &mut Frame, area: Rect, page: &Page) {
+    let block = Block::default().title(" Preview ").borders(Borders::ALL);
+    let inner = block.inner(area);
+    let text: Vec<Line> = preview::page_preview(page, inner.width, inner.height)
+        .into_iter()
+        .map(Line::from)
+        .collect();
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
+
+/// A terminal event as the main loop acts on it.
+#[derive(Debug, PartialEq)]
+enum Input {
+    Key(KeyEvent),
+    /// The terminal changed size; everything must be redrawn.
+    Redraw,
+    Ignore,
+}
+
+fn dispatch(event: Event) -> Input {
+    match event {
+        Event::Key(key) => Input::Key(key),
+        Event::Resize(_, _) => Input::Redraw,
+        _ => Input::Ignore,
+    }
+}
+
 fn main() -> io::Result<()> {
+    let page = match std::env::args().nth(1).map(PathBuf::from) {
+        Some(dir) => match load_first_page(&dir) {
+            Ok(page) => Some(page),
+            Err(e) => {
+                eprintln!("grids-viewer: {e}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let mut viewer = Viewer {
+        page,
+        unit: Unit::Pt,
+    };
+
     enable_raw_mode()?;
     io::stdout().execute(EnterAlternateScreen)?;
 
@@ -18,19 +146,41 @@ fn main() -> io::Result<()> {
 
     loop {
         terminal.draw(|frame| {
+            let area = match &viewer.page {
+                Some(page) => {
+                    let halves = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(frame.area());
+                    draw_preview(frame, halves[1], page);
+                    halves[0]
+                }
+                None => frame.area(),
+            };
             let block = Block::default()
                 .title(" GRIDS viewer ")
                 .borders(Borders::ALL);
-            let paragraph = Paragraph::new("Press 'q' to quit.")
+            let text: Vec<Line> = viewer.lines().into_iter().map(Line::from).collect();
+            let paragraph = Paragraph::new(text)
                 .block(block)
                 .alignment(Alignment::Center);
-            frame.render_widget(paragraph, frame.area());
+            frame.render_widget(paragraph, area);
         })?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.code == KeyCode::Char('q') {
-                break;
+        let key = match dispatch(event::read()?) {
+            Input::Key(key) => key,
+            // Repaint from scratch so cells from the old size don't linger.
+            Input::Redraw => {
+                terminal.clear()?;
+                continue;
             }
+            Input::Ignore => continue,
+        };
+
+        match key.code {
+            KeyCode::Char('q') => break,
+            KeyCode::Char('u') => viewer.unit = viewer.unit.next(),
+            _ => {}
         }
     }
 
@@ -38,3 +188,16 @@ fn main() -> io::Result<()> {
     io::stdout().execute(LeaveAlternateScreen)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_alone_asks_for_a_redraw() {
+        assert_eq!(dispatch(Event::Resize(120, 40)), Input::Redraw);
+        let key = KeyEvent::from(KeyCode::Char('u'));
+        assert_eq!(dispatch(Event::Key(key)), Input::Key(key));
+        assert_eq!(dispatch(Event::FocusGained), Input::Ignore);
+    }
+}
@@ -0,0 +1,118 @@
+//! A coarse character-cell drawing of a page: grid cells dotted in, blocks
+//! boxed with their content type's initial and id. Good enough to sanity-check
+//! a layout without opening the SVG.
+
+use grids_layout::grid::Rect;
+use grids_layout::page::{BlockContent, Page};
+
+/// Terminal cells are roughly twice as tall as they are wide.
+const CELL_ASPECT: f64 = 2.0;
+
+/// Draw `page` into at most `cols` x `rows` characters, keeping its
+/// proportions. One string per row.
+pub fn page_preview(page: &Page, cols: u16, rows: u16) -> Vec<String> {
+    let (pw, ph) = page.size.dimensions();
+    if cols < 2 || rows < 2 || pw <= 0.0 || ph <= 0.0 {
+        return Vec::new();
+    }
+    // Points per character horizontally; vertically it's CELL_ASPECT times
+    // that.
+    let scale = (pw / cols as f64).max(ph / (rows as f64 * CELL_ASPECT));
+    let mut canvas = Canvas::new(
+        (pw / scale).round().max(2.0) as usize,
+        (ph / (scale * CELL_ASPECT)).round().max(2.0) as usize,
+        scale,
+    );
+
+    canvas.boxed(Rect::new(0.0, 0.0, pw, ph));
+    let grid = &page.grid;
+    for (col, row) in grid.cells() {
+        canvas.fill(grid.cell_rect(col, row, 1, 1), '·');
+    }
+    for block in &page.blocks {
+        let rect = grid.cell_rect(block.col, block.row, block.col_span, block.row_span);
+        canvas.fill(rect, ' ');
+        canvas.boxed(rect);
+        canvas.label(rect, &format!("{} {}", content_initial(&block.content), block.id));
+    }
+    canvas.lines()
+}
+
+/// A one- or two-letter tag for the kind of content in a block.
+fn content_initial(content: &BlockContent) -> &'static str {
+    match content {
+        BlockContent::Text { .. } => "T",
+        BlockContent::Image { .. } => "I",
+        BlockContent::Qr { .. } => "Q",
+        BlockContent::Table { .. } => "Tb",
+        BlockContent::Fill { .. } => "F",
+        BlockContent::Empty => "E",
+    }
+}
+
+struct Canvas {
+    cells: Vec<Vec<char>>,
+    scale: f64,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize, scale: f64) -> Self {
+        Self {
+            cells: vec![vec![' '; width]; height],
+            scale,
+        }
+    }
+
+    /// The character columns and rows `rect` covers, inclusive, clamped to
+    /// the canvas.
+    fn span(&self, rect: Rect) -> (usize, usize, usize, usize) {
+        let width = self.cells[0].len();
+        let height = self.cells.len();
+        let col = |x: f64| ((x / self.scale).round().max(0.0) as usize).min(width - 1);
+        let row = |y: f64| {
+            ((y / (self.scale * CELL_ASPECT)).round().max(0.0) as usize).min(height - 1)
+        };
+        let (x0, y0) = (col(rect.x), row(rect.y));
+        let (x1, y1) = (col(rect.x + rect.w).max(x0), row(rect.y + rect.h).max(y0));
+        (x0, y0, x1, y1)
+    }
+
+    fn fill(&mut self, rect: Rect, ch: char) {
+        let (x0, y0, x1, y1) = self.span(rect);
+        for row in &mut self.cells[y0..=y1] {
+            row[x0..=x1].fill(ch);
+        }
+    }
+
+    fn boxed(&mut self, rect: Rect) {
+        let (x0, y0, x1, y1) = self.span(rect);
+        for x in x0..=x1 {
+            self.cells[y0][x] = '─';
+            self.cells[y1][x] = '─';
+        }
+        for row in &mut self.cells[y0..=y1] {
+            row[x0] = '│';
+            row[x1] = '│';
+        }
+        self.cells[y0][x0] = '┌';
+        self.cells[y0][x1] = '┐';
+        self.cells[y1][x0] = '└';
+        self.cells[y1][x1] = '┘';
+    }
+
+    /// Write `text` just inside the top-left corner of `rect`, cut to fit.
+    fn label(&mut self, rect: Rect, text: &str) {
+        let (x0, y0, x1, y1) = self.span(rect);
+        if x1 < x0 + 2 || y1 < y0 + 2 {
+            return;
+        }
+        let row = &mut self.cells[y0 + 1];
+        for (slot, ch) in row[x0 + 1..x1].iter_mut().zip(text.chars()) {
+            *slot = ch;
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        self.cells.iter().map(|row| row.iter().collect()).collect()
+    }
+}